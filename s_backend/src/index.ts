@@ -12,6 +12,7 @@ import {
   CasperClient,
   Contracts,
 } from "casper-js-sdk";
+import { keyListToAccountHashes, mapEntries, publicUsers } from "./keys";
 
 dotenv.config();
 
@@ -377,34 +378,36 @@ app.post("/api/find-users", async (req, res) => {
       // The data is returned as an array of [mediaIdHash, users[]] pairs
       let users: string[] = [];
 
-      if (Array.isArray(mediaCompletersData)) {
-        // BTreeMap is returned as an array of [mediaIdHash, userKeys[]] pairs
-        for (const entry of mediaCompletersData) {
-          if (Array.isArray(entry) && entry.length === 2) {
-            const [key, value] = entry;
-            // Check if this is our mediaIdHash entry
-            if (key === mediaIdHash || String(key) === mediaIdHash) {
-              // Value is a CLValue containing an array of Keys
-              const valueJson = JSON.stringify(value);
-              try {
-                const parsedValue = JSON.parse(valueJson);
-                if (Array.isArray(parsedValue)) {
-                  users = parsedValue.map((userKey: any) => String(userKey));
-                }
-              } catch (e) {
-                console.error("[API] Failed to parse users value:", e);
-              }
-              break;
-            }
-          }
+      // BTreeMap is returned as [mediaIdHash, userKeys[]] pairs
+      for (const [key, value] of mapEntries(mediaCompletersData)) {
+        // Check if this is our mediaIdHash entry
+        if (key === mediaIdHash || String(key) === mediaIdHash) {
+          // Keys are decoded to account-hash hex, as in /api/user-nfts
+          users = keyListToAccountHashes(value);
+          break;
         }
       }
 
       console.log("[API] Users who completed media:", users);
 
+      // Honor per-user visibility: 0 = public, 1 = co-completers only,
+      // 2 = hidden. This endpoint does not authenticate the caller, so it
+      // only lists public users; co-completers are found through the
+      // contract's media_completers_page, which checks the calling key.
+      // The map only exists once someone changed their setting.
+      let visibilityData: unknown = [];
+      try {
+        visibilityData = await contract.queryContractData(["visibility"]);
+      } catch (e) {
+        console.log("[API] No visibility settings stored yet");
+      }
+
+      const visibleUsers = publicUsers(users, visibilityData);
+
+      // Non-public users are left out of the list but still counted.
       res.json({
         success: true,
-        users,
+        users: visibleUsers,
         count: users.length,
       });
     } catch (queryError: any) {
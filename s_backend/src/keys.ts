@@ -0,0 +1,93 @@
+// Contract storage queries return keys in different shapes depending on the
+// SDK version: hex strings (bare or "account-hash-..."), byte arrays, or
+// CLValue objects wrapping either. These helpers reduce them all to the
+// lowercase account-hash hex that the API compares and returns.
+
+const HASH_HEX = /[0-9a-f]{64}/i;
+
+export function keyToAccountHashHex(key: unknown): string {
+  if (typeof key === "string") {
+    const match = key.match(HASH_HEX);
+    return match ? match[0].toLowerCase() : "";
+  }
+  if (key instanceof Uint8Array) {
+    return Buffer.from(key).toString("hex");
+  }
+  if (Array.isArray(key) && key.every((byte) => typeof byte === "number")) {
+    return Buffer.from(key as number[]).toString("hex");
+  }
+  if (key && typeof key === "object") {
+    const wrapped = key as { data?: unknown; value?: () => unknown };
+    if (wrapped.data !== undefined) {
+      return keyToAccountHashHex(wrapped.data);
+    }
+    if (typeof wrapped.value === "function") {
+      return keyToAccountHashHex(wrapped.value());
+    }
+  }
+  return "";
+}
+
+// BTreeMaps come back either as [key, value] pairs or as a Map.
+export function mapEntries(data: unknown): Array<[unknown, unknown]> {
+  if (data instanceof Map) {
+    return Array.from(data.entries());
+  }
+  if (Array.isArray(data)) {
+    return data.filter(
+      (entry): entry is [unknown, unknown] =>
+        Array.isArray(entry) && entry.length === 2
+    );
+  }
+  return [];
+}
+
+export function keyListToAccountHashes(data: unknown): string[] {
+  let list: unknown = data;
+  if (list && typeof list === "object" && !Array.isArray(list)) {
+    const wrapped = list as { data?: unknown; value?: () => unknown };
+    if (wrapped.data !== undefined) {
+      list = wrapped.data;
+    } else if (typeof wrapped.value === "function") {
+      list = wrapped.value();
+    }
+  }
+  if (!Array.isArray(list)) {
+    return [];
+  }
+  return list.map(keyToAccountHashHex).filter((hash) => hash !== "");
+}
+
+function toNumber(value: unknown): number {
+  if (typeof value === "number") return value;
+  if (typeof value === "string" || typeof value === "bigint") {
+    return Number(value);
+  }
+  if (value && typeof value === "object") {
+    const wrapped = value as {
+      toNumber?: () => number;
+      data?: unknown;
+      value?: () => unknown;
+    };
+    if (typeof wrapped.toNumber === "function") return wrapped.toNumber();
+    if (wrapped.data !== undefined) return toNumber(wrapped.data);
+    if (typeof wrapped.value === "function") return toNumber(wrapped.value());
+  }
+  return Number.NaN;
+}
+
+// Visibility: 0 = public, 1 = co-completers only, 2 = hidden. Users with no
+// entry are public. A setting that cannot be decoded hides the user.
+export function publicUsers(
+  users: string[],
+  visibilityData: unknown
+): string[] {
+  const visibility = new Map<string, number>();
+  for (const [key, value] of mapEntries(visibilityData)) {
+    const hash = keyToAccountHashHex(key);
+    if (hash !== "") {
+      visibility.set(hash, toNumber(value));
+    }
+  }
+  return users.filter((user) => (visibility.get(user) ?? 0) === 0);
+}
@@ -0,0 +1,54 @@
+import { test } from "node:test";
+import assert from "node:assert/strict";
+import {
+  keyListToAccountHashes,
+  keyToAccountHashHex,
+  publicUsers,
+} from "../src/keys";
+
+const ALICE = "a1".repeat(32);
+const BOB = "b2".repeat(32);
+
+// Shape of a Key::Account as returned by casper-js-sdk: a CLKey wrapping a
+// CLAccountHash, which holds the raw bytes.
+function sdkKey(hex: string) {
+  const bytes = Uint8Array.from(Buffer.from(hex, "hex"));
+  return { data: { data: bytes, value: () => bytes } };
+}
+
+test("decodes every key shape to the same account-hash hex", () => {
+  const bytes = Buffer.from(ALICE, "hex");
+  assert.equal(keyToAccountHashHex(ALICE.toUpperCase()), ALICE);
+  assert.equal(keyToAccountHashHex(`account-hash-${ALICE}`), ALICE);
+  assert.equal(keyToAccountHashHex(bytes), ALICE);
+  assert.equal(keyToAccountHashHex(Array.from(bytes)), ALICE);
+  assert.equal(keyToAccountHashHex(sdkKey(ALICE)), ALICE);
+  assert.equal(keyToAccountHashHex({ value: () => bytes }), ALICE);
+  assert.equal(keyToAccountHashHex({}), "");
+});
+
+test("decodes completer lists from SDK key objects", () => {
+  assert.deepEqual(keyListToAccountHashes([sdkKey(ALICE), sdkKey(BOB)]), [
+    ALICE,
+    BOB,
+  ]);
+  assert.deepEqual(keyListToAccountHashes({ data: [sdkKey(BOB)] }), [BOB]);
+});
+
+test("hides only the users who chose a non-public setting", () => {
+  const users = [ALICE, BOB];
+
+  assert.deepEqual(publicUsers(users, []), users);
+  assert.deepEqual(publicUsers(users, [[sdkKey(BOB), 1]]), [ALICE]);
+  assert.deepEqual(
+    publicUsers(users, new Map([[sdkKey(ALICE), { toNumber: () => 2 }]])),
+    [BOB]
+  );
+  assert.deepEqual(
+    publicUsers(users, [
+      [sdkKey(ALICE), 0],
+      [`account-hash-${BOB}`, "2"],
+    ]),
+    [ALICE]
+  );
+});
@@ -7,22 +7,31 @@ use alloc::{
     vec::Vec,
 };
 use casper_contract::{
-    contract_api::{runtime, storage},
+    contract_api::{cryptography, runtime, storage, system},
     unwrap_or_revert::UnwrapOrRevert,
 };
 use casper_types::{
     account::AccountHash,
     api_error::ApiError,
-    contracts::NamedKeys,
-    CLType, CLValue, EntityEntryPoint as EntryPoint, EntryPointAccess, EntryPointPayment,
-    EntryPointType, EntryPoints, Key, Parameter, URef, U256,
+    bytesrepr::{self, Bytes, FromBytes, ToBytes},
+    contracts::{ContractHash, ContractPackageHash, NamedKeys},
+    runtime_args,
+    system::CallerInfo,
+    CLType, CLTyped, CLValue, EntityEntryPoint as EntryPoint, EntryPointAccess, EntryPointPayment,
+    EntityAddr, EntryPointType, EntryPoints, Key, Parameter, PublicKey, Signature, URef, U256, U512,
 };
 
 const CONTRACT_NAME: &str = "media_nft_contract";
 
+const CALLER_ACCOUNT: u8 = 0;
+const CALLER_CONTRACT_PACKAGE: u8 = 2;
+const CALLER_ENTITY: u8 = 3;
+const CALLER_CONTRACT: u8 = 4;
+
 const OWNER_KEY: &str = "owner";
 const BACKEND_KEY: &str = "backend";
 const BASE_URI_KEY: &str = "base_uri";
+const KIND_BASE_URI_KEY: &str = "kind_base_uri";
 const NEXT_TOKEN_ID_KEY: &str = "next_token_id";
 
 const MEDIA_KEY: &str = "media";
@@ -39,8 +48,133 @@ const MEDIA_COMPLETER_INDEX_PLUS_ONE_KEY: &str = "media_completer_index_plus_one
 const GROUP_MEMBERS_KEY: &str = "group_members";
 const GROUP_INDEX_PLUS_ONE_KEY: &str = "group_index_plus_one";
 
+const ROOMS_KEY: &str = "rooms";
+const MEDIA_ROOMS_KEY: &str = "media_rooms";
+const GROUP_POLICY_KEY: &str = "group_policy";
+const GROUP_RULES_KEY: &str = "group_rules";
+const MEDIA_RULE_GROUPS_KEY: &str = "media_rule_groups";
+const KIND_RULE_GROUPS_KEY: &str = "kind_rule_groups";
+const GROUP_INVITES_KEY: &str = "group_invites";
+const JOIN_REQUESTS_KEY: &str = "join_requests";
+const JOIN_REQUEST_INDEX_PLUS_ONE_KEY: &str = "join_request_index_plus_one";
+
+const MESSAGING_PREFS_KEY: &str = "messaging_prefs";
+const BLOCKS_KEY: &str = "blocks";
+const FOLLOWERS_KEY: &str = "followers";
+const FOLLOWER_INDEX_PLUS_ONE_KEY: &str = "follower_index_plus_one";
+const FOLLOWING_KEY: &str = "following";
+const FOLLOWING_INDEX_PLUS_ONE_KEY: &str = "following_index_plus_one";
+const FOLLOW_GATED_KEY: &str = "follow_gated";
+
+const TOKEN_RATING_KEY: &str = "token_rating";
+const TOKEN_REVIEW_KEY: &str = "token_review";
+const MEDIA_RATING_KEY: &str = "media_rating";
+
+const ACHIEVEMENTS_KEY: &str = "achievements";
+const USER_ACHIEVEMENTS_KEY: &str = "user_achievements";
+const ACHIEVEMENT_AWARDED_KEY: &str = "achievement_awarded";
+const NEXT_ACHIEVEMENT_TOKEN_ID_KEY: &str = "next_achievement_token_id";
+const ACHIEVEMENT_TOKENS_KEY: &str = "achievement_tokens";
+const FIRST_COMPLETERS_KEY: &str = "first_completers";
+
+const MEDIA_EDITION_COUNTER_KEY: &str = "media_edition_counter";
+const TOKEN_INFO_KEY: &str = "token_info";
+const FIRST_EDITION_LIMIT_KEY: &str = "first_edition_limit";
+
+const TOKEN_METADATA_DICT: &str = "token_metadata";
+const METADATA_POLICY_KEY: &str = "metadata_policy";
+
+const REVOCATIONS_KEY: &str = "revocations";
+const REPEAT_COMPLETIONS_KEY: &str = "repeat_completions_allowed";
+const COMPLETION_COUNT_KEY: &str = "completion_count";
+
+const PAUSED_SCOPES_KEY: &str = "paused_scopes";
+const MINT_LIMITS_KEY: &str = "mint_limits";
+const MINTER_WINDOWS_KEY: &str = "minter_windows";
+const MINTER_USER_WINDOWS_KEY: &str = "minter_user_windows";
+
 const REGISTRAR_KEY: &str = "registrar";
 
+const HOOKS_KEY: &str = "hooks";
+const HOOKS_DISABLED_KEY: &str = "hooks_disabled";
+
+const REWARD_TOKEN_KEY: &str = "reward_token";
+const REWARD_TABLE_KEY: &str = "reward_table";
+const TOTAL_REWARDS_PAID_KEY: &str = "total_rewards_paid";
+const REWARDED_COMPLETIONS_KEY: &str = "rewarded_completions";
+
+const PAID_MINTING_KEY: &str = "paid_minting_enabled";
+const ATTESTOR_KEY: &str = "attestor";
+const MINT_FEES_KEY: &str = "mint_fees";
+const FEE_PURSE_KEY: &str = "fee_purse";
+const CONSUMED_ATTESTATIONS_KEY: &str = "consumed_attestations";
+
+const CAMPAIGNS_KEY: &str = "campaigns";
+const TOKEN_CAMPAIGN_URI_KEY: &str = "token_campaign_uri";
+
+const CHALLENGES_KEY: &str = "challenges";
+const CHALLENGE_PROGRESS_KEY: &str = "challenge_progress";
+const USER_CHALLENGES_KEY: &str = "user_challenges";
+const CHALLENGE_LEADERBOARD_KEY: &str = "challenge_leaderboard";
+
+const USER_STATS_KEY: &str = "user_stats";
+
+const VISIBILITY_KEY: &str = "visibility";
+
+const PRIVATE_COMMITMENTS_KEY: &str = "private_commitments";
+const REVEALED_COMPLETIONS_KEY: &str = "revealed_completions";
+
+const JOIN_POLICY_OPEN: u8 = 0;
+const JOIN_POLICY_INVITE_ONLY: u8 = 1;
+const JOIN_POLICY_APPROVAL: u8 = 2;
+
+const RULE_ALL_OF: u8 = 0;
+const RULE_ANY_OF: u8 = 1;
+const RULE_KIND_COUNT: u8 = 2;
+
+const MESSAGING_CO_COMPLETERS: u8 = 0;
+const MESSAGING_MUTUAL_FOLLOWS: u8 = 1;
+const MESSAGING_NOBODY: u8 = 2;
+
+const VISIBILITY_PUBLIC: u8 = 0;
+const VISIBILITY_CO_COMPLETERS: u8 = 1;
+const VISIBILITY_HIDDEN: u8 = 2;
+
+const RATING_MIN: u8 = 1;
+const RATING_MAX: u8 = 10;
+const MAX_REVIEW_LEN: usize = 1024;
+
+const ACHIEVEMENT_TOTAL_COMPLETIONS: u8 = 0;
+const ACHIEVEMENT_KIND_COUNT: u8 = 1;
+const ACHIEVEMENT_ALL_KINDS: u8 = 2;
+const ACHIEVEMENT_FIRST_COMPLETER: u8 = 3;
+
+const MEDIA_KINDS: [u8; 6] = [1, 2, 3, 4, 5, 6];
+
+const DEFAULT_FIRST_EDITION_LIMIT: u64 = 100;
+
+const LEADERBOARD_SIZE: usize = 10;
+
+const METADATA_IMMUTABLE: u8 = 0;
+const METADATA_OWNER_UPDATABLE: u8 = 1;
+const METADATA_BACKEND_UPDATABLE: u8 = 2;
+
+const SVG_TITLE_MAX_CHARS: usize = 28;
+
+const PAUSE_MINTING: u8 = 1;
+const PAUSE_BURNING: u8 = 2;
+const PAUSE_GROUPS: u8 = 4;
+const PAUSE_METADATA: u8 = 8;
+const PAUSE_ALL_SCOPES: u8 = PAUSE_MINTING | PAUSE_BURNING | PAUSE_GROUPS | PAUSE_METADATA;
+
+const MAX_HOOKS: usize = 8;
+const HOOK_ON_COMPLETION: &str = "on_completion";
+const HOOK_ON_BURN: &str = "on_burn";
+
+const INTERFACE_COMPLETIONS_V1: &str = "media_nft.completions.v1";
+const INTERFACE_SOCIAL_V1: &str = "media_nft.social.v1";
+const SUPPORTED_INTERFACES: [&str; 2] = [INTERFACE_COMPLETIONS_V1, INTERFACE_SOCIAL_V1];
+
 const ARG_TO: &str = "to";
 const ARG_KIND: &str = "kind";
 const ARG_URI: &str = "uri";
@@ -55,6 +189,53 @@ const ARG_BASE_URI: &str = "base_uri";
 const ARG_REGISTRAR: &str = "registrar";
 const ARG_ALLOWED: &str = "allowed";
 const ARG_BACKEND: &str = "backend";
+const ARG_ROOM_ID: &str = "room_id";
+const ARG_JOIN_POLICY: &str = "join_policy";
+const ARG_START: &str = "start";
+const ARG_COUNT: &str = "count";
+const ARG_GROUP_ID: &str = "group_id";
+const ARG_RULE: &str = "rule";
+const ARG_REQUIRED: &str = "required";
+const ARG_MEDIA_IDS: &str = "media_ids";
+const ARG_PREFERENCE: &str = "preference";
+const ARG_TARGET: &str = "target";
+const ARG_ENABLED: &str = "enabled";
+const ARG_RATING: &str = "rating";
+const ARG_REVIEW: &str = "review";
+const ARG_ACHIEVEMENT_ID: &str = "achievement_id";
+const ARG_THRESHOLD: &str = "threshold";
+const ARG_POLICY: &str = "policy";
+const ARG_PLATFORM: &str = "platform";
+const ARG_WATCH_TIME: &str = "watch_time";
+const ARG_REASON_CODE: &str = "reason_code";
+const ARG_COOLDOWN: &str = "cooldown";
+const ARG_SCOPES: &str = "scopes";
+const ARG_WINDOW: &str = "window";
+const ARG_PER_USER_LIMIT: &str = "per_user_limit";
+const ARG_PER_MINTER_LIMIT: &str = "per_minter_limit";
+const ARG_INTERFACE_ID: &str = "interface_id";
+const ARG_HOOK: &str = "hook";
+const ARG_CAN_VETO: &str = "can_veto";
+const ARG_REWARD_TOKEN: &str = "reward_token";
+const ARG_AMOUNT: &str = "amount";
+const ARG_RECIPIENT: &str = "recipient";
+const ARG_OWNER: &str = "owner";
+const ARG_ADDRESS: &str = "address";
+const ARG_ATTESTOR: &str = "attestor";
+const ARG_FEE: &str = "fee";
+const ARG_EXPIRES_AT: &str = "expires_at";
+const ARG_SIGNATURE: &str = "signature";
+const ARG_PURSE: &str = "purse";
+const ARG_TREASURY: &str = "treasury";
+const ARG_CAP: &str = "cap";
+const ARG_START_TIME: &str = "start_time";
+const ARG_END_TIME: &str = "end_time";
+const ARG_EDITION_URI: &str = "edition_uri";
+const ARG_CHALLENGE_ID: &str = "challenge_id";
+const ARG_VISIBILITY: &str = "visibility";
+const ARG_COMMITMENT: &str = "commitment";
+const ARG_SALT: &str = "salt";
+const ARG_FIRST_EDITION_LIMIT: &str = "first_edition_limit";
 
 #[no_mangle]
 pub extern "C" fn call() {
@@ -65,12 +246,14 @@ pub extern "C" fn call() {
     let backend_uref = storage::new_uref(Key::Account(caller));
     let base_uri_uref = storage::new_uref(String::new());
     let next_token_id_uref = storage::new_uref(U256::one());
+    let first_edition_limit_uref = storage::new_uref(DEFAULT_FIRST_EDITION_LIMIT);
 
     let mut named_keys = NamedKeys::new();
     named_keys.insert(String::from(OWNER_KEY), owner_uref.into());
     named_keys.insert(String::from(BACKEND_KEY), backend_uref.into());
     named_keys.insert(String::from(BASE_URI_KEY), base_uri_uref.into());
     named_keys.insert(String::from(NEXT_TOKEN_ID_KEY), next_token_id_uref.into());
+    named_keys.insert(String::from(FIRST_EDITION_LIMIT_KEY), first_edition_limit_uref.into());
 
     let (contract_hash, _contract_version) = storage::new_contract(
         entry_points,
@@ -256,6 +439,19 @@ fn create_entry_points() -> EntryPoints {
         EntryPointPayment::Caller,
     ));
 
+    entry_points.add_entry_point(EntryPoint::new(
+        "group_members_page",
+        vec![
+            Parameter::new(ARG_MEDIA_ID, CLType::String),
+            Parameter::new(ARG_START, CLType::U256),
+            Parameter::new(ARG_COUNT, CLType::U256),
+        ],
+        CLType::List(Box::new(CLType::Key)),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
     entry_points.add_entry_point(EntryPoint::new(
         "group_member_at",
         vec![
@@ -322,560 +518,4659 @@ fn create_entry_points() -> EntryPoints {
         EntryPointPayment::Caller,
     ));
 
-    entry_points
-}
+    entry_points.add_entry_point(EntryPoint::new(
+        "create_room",
+        vec![
+            Parameter::new(ARG_MEDIA_ID, CLType::String),
+            Parameter::new(ARG_NAME, CLType::String),
+            Parameter::new(ARG_JOIN_POLICY, CLType::U8),
+        ],
+        CLType::String,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
 
-fn get_uref(key: &str) -> URef {
-    runtime::get_key(key)
-        .and_then(Key::into_uref)
-        .unwrap_or_revert_with(ApiError::MissingKey)
-}
+    entry_points.add_entry_point(EntryPoint::new(
+        "join_room",
+        vec![Parameter::new(ARG_ROOM_ID, CLType::String)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
 
-fn get_or_init_uref<T: Default>(key: &str) -> URef
-where
-    T: casper_types::bytesrepr::FromBytes + casper_types::bytesrepr::ToBytes + casper_types::CLTyped,
-{
-    if let Some(uref) = runtime::get_key(key).and_then(Key::into_uref) {
-        return uref;
-    }
-    let uref = storage::new_uref(T::default());
-    runtime::put_key(key, uref.into());
-    uref
-}
+    entry_points.add_entry_point(EntryPoint::new(
+        "leave_room",
+        vec![Parameter::new(ARG_ROOM_ID, CLType::String)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
 
-fn require_owner() {
-    let owner: AccountHash = storage::read(get_uref(OWNER_KEY))
-        .unwrap_or_revert_with(ApiError::Read)
-        .unwrap_or_revert();
-    if runtime::get_caller() != owner {
-        runtime::revert(ApiError::User(1));
-    }
-}
+    entry_points.add_entry_point(EntryPoint::new(
+        "room_info",
+        vec![Parameter::new(ARG_ROOM_ID, CLType::String)],
+        CLType::Tuple3([
+            Box::new(CLType::String),
+            Box::new(CLType::Key),
+            Box::new(CLType::Tuple2([Box::new(CLType::String), Box::new(CLType::U8)])),
+        ]),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
 
-fn require_backend() {
-    let backend: Key = storage::read(get_uref(BACKEND_KEY))
-        .unwrap_or_revert_with(ApiError::Read)
-        .unwrap_or_revert();
-    let caller = Key::Account(runtime::get_caller());
-    if caller != backend {
-        runtime::revert(ApiError::User(2));
-    }
-}
+    entry_points.add_entry_point(EntryPoint::new(
+        "media_rooms",
+        vec![Parameter::new(ARG_MEDIA_ID, CLType::String)],
+        CLType::List(Box::new(CLType::String)),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
 
-fn get_next_token_id() -> U256 {
-    storage::read(get_uref(NEXT_TOKEN_ID_KEY))
-        .unwrap_or_revert_with(ApiError::Read)
-        .unwrap_or(U256::one())
-}
+    entry_points.add_entry_point(EntryPoint::new(
+        "room_members_page",
+        vec![
+            Parameter::new(ARG_ROOM_ID, CLType::String),
+            Parameter::new(ARG_START, CLType::U256),
+            Parameter::new(ARG_COUNT, CLType::U256),
+        ],
+        CLType::List(Box::new(CLType::Key)),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
 
-fn set_next_token_id(v: U256) {
-    storage::write(get_uref(NEXT_TOKEN_ID_KEY), v);
-}
+    entry_points.add_entry_point(EntryPoint::new(
+        "create_group",
+        vec![
+            Parameter::new(ARG_NAME, CLType::String),
+            Parameter::new(ARG_RULE, CLType::U8),
+            Parameter::new(ARG_KIND, CLType::U8),
+            Parameter::new(ARG_REQUIRED, CLType::U64),
+            Parameter::new(ARG_MEDIA_IDS, CLType::List(Box::new(CLType::String))),
+            Parameter::new(ARG_JOIN_POLICY, CLType::U8),
+        ],
+        CLType::String,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
 
-fn get_media() -> BTreeMap<String, (u8, bool, (String, String))> {
-    storage::read(get_or_init_uref::<BTreeMap<String, (u8, bool, (String, String))>>(MEDIA_KEY))
-        .unwrap_or_revert_with(ApiError::Read)
-        .unwrap_or_default()
-}
+    entry_points.add_entry_point(EntryPoint::new(
+        "group_info",
+        vec![Parameter::new(ARG_GROUP_ID, CLType::String)],
+        CLType::Tuple3([
+            Box::new(CLType::Tuple2([Box::new(CLType::Key), Box::new(CLType::String)])),
+            Box::new(CLType::Tuple3([
+                Box::new(CLType::U8),
+                Box::new(CLType::U8),
+                Box::new(CLType::U64),
+            ])),
+            Box::new(CLType::List(Box::new(CLType::String))),
+        ]),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
 
-fn set_media(v: BTreeMap<String, (u8, bool, (String, String))>) {
-    storage::write(get_or_init_uref::<BTreeMap<String, (u8, bool, (String, String))>>(MEDIA_KEY), v);
-}
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_group_policy",
+        vec![
+            Parameter::new(ARG_GROUP_ID, CLType::String),
+            Parameter::new(ARG_JOIN_POLICY, CLType::U8),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
 
-fn get_token_owner() -> BTreeMap<U256, Key> {
-    storage::read(get_or_init_uref::<BTreeMap<U256, Key>>(TOKEN_OWNER_KEY))
-        .unwrap_or_revert_with(ApiError::Read)
-        .unwrap_or_default()
-}
+    entry_points.add_entry_point(EntryPoint::new(
+        "invite_to_group",
+        vec![
+            Parameter::new(ARG_GROUP_ID, CLType::String),
+            Parameter::new(ARG_USER, CLType::Key),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
 
-fn set_token_owner(v: BTreeMap<U256, Key>) {
-    storage::write(get_or_init_uref::<BTreeMap<U256, Key>>(TOKEN_OWNER_KEY), v);
-}
+    entry_points.add_entry_point(EntryPoint::new(
+        "accept_invite",
+        vec![Parameter::new(ARG_GROUP_ID, CLType::String)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
 
-fn get_token_media_id() -> BTreeMap<U256, String> {
-    storage::read(get_or_init_uref::<BTreeMap<U256, String>>(TOKEN_MEDIA_ID_KEY))
-        .unwrap_or_revert_with(ApiError::Read)
-        .unwrap_or_default()
-}
+    entry_points.add_entry_point(EntryPoint::new(
+        "has_invite",
+        vec![
+            Parameter::new(ARG_GROUP_ID, CLType::String),
+            Parameter::new(ARG_USER, CLType::Key),
+        ],
+        CLType::Bool,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
 
-fn set_token_media_id(v: BTreeMap<U256, String>) {
-    storage::write(get_or_init_uref::<BTreeMap<U256, String>>(TOKEN_MEDIA_ID_KEY), v);
-}
+    entry_points.add_entry_point(EntryPoint::new(
+        "request_to_join",
+        vec![Parameter::new(ARG_GROUP_ID, CLType::String)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
 
-fn get_completion_token_id() -> BTreeMap<(Key, String), U256> {
-    storage::read(get_or_init_uref::<BTreeMap<(Key, String), U256>>(COMPLETION_TOKEN_ID_KEY))
-        .unwrap_or_revert_with(ApiError::Read)
-        .unwrap_or_default()
-}
+    entry_points.add_entry_point(EntryPoint::new(
+        "approve_join_request",
+        vec![
+            Parameter::new(ARG_GROUP_ID, CLType::String),
+            Parameter::new(ARG_USER, CLType::Key),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
 
-fn set_completion_token_id(v: BTreeMap<(Key, String), U256>) {
-    storage::write(get_or_init_uref::<BTreeMap<(Key, String), U256>>(COMPLETION_TOKEN_ID_KEY), v);
-}
+    entry_points.add_entry_point(EntryPoint::new(
+        "reject_join_request",
+        vec![
+            Parameter::new(ARG_GROUP_ID, CLType::String),
+            Parameter::new(ARG_USER, CLType::Key),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
 
-fn get_user_token_ids() -> BTreeMap<Key, Vec<U256>> {
-    storage::read(get_or_init_uref::<BTreeMap<Key, Vec<U256>>>(USER_TOKEN_IDS_KEY))
-        .unwrap_or_revert_with(ApiError::Read)
-        .unwrap_or_default()
-}
+    entry_points.add_entry_point(EntryPoint::new(
+        "join_request_count",
+        vec![Parameter::new(ARG_GROUP_ID, CLType::String)],
+        CLType::U256,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
 
-fn set_user_token_ids(v: BTreeMap<Key, Vec<U256>>) {
-    storage::write(get_or_init_uref::<BTreeMap<Key, Vec<U256>>>(USER_TOKEN_IDS_KEY), v);
-}
+    entry_points.add_entry_point(EntryPoint::new(
+        "join_requests_page",
+        vec![
+            Parameter::new(ARG_GROUP_ID, CLType::String),
+            Parameter::new(ARG_START, CLType::U256),
+            Parameter::new(ARG_COUNT, CLType::U256),
+        ],
+        CLType::List(Box::new(CLType::Key)),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
 
-fn get_user_token_index_plus_one() -> BTreeMap<(Key, U256), U256> {
-    storage::read(get_or_init_uref::<BTreeMap<(Key, U256), U256>>(USER_TOKEN_INDEX_PLUS_ONE_KEY))
-        .unwrap_or_revert_with(ApiError::Read)
-        .unwrap_or_default()
-}
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_messaging_preference",
+        vec![Parameter::new(ARG_PREFERENCE, CLType::U8)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
 
-fn set_user_token_index_plus_one(v: BTreeMap<(Key, U256), U256>) {
-    storage::write(get_or_init_uref::<BTreeMap<(Key, U256), U256>>(USER_TOKEN_INDEX_PLUS_ONE_KEY), v);
+    entry_points.add_entry_point(EntryPoint::new(
+        "messaging_preference",
+        vec![Parameter::new(ARG_USER, CLType::Key)],
+        CLType::U8,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "block_user",
+        vec![Parameter::new(ARG_USER, CLType::Key)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "unblock_user",
+        vec![Parameter::new(ARG_USER, CLType::Key)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "is_blocked",
+        vec![
+            Parameter::new(ARG_USER, CLType::Key),
+            Parameter::new(ARG_TARGET, CLType::Key),
+        ],
+        CLType::Bool,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "can_message",
+        vec![
+            Parameter::new(ARG_FROM, CLType::Key),
+            Parameter::new(ARG_TO, CLType::Key),
+        ],
+        CLType::Bool,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_follow_gated",
+        vec![Parameter::new(ARG_ENABLED, CLType::Bool)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "follow",
+        vec![Parameter::new(ARG_USER, CLType::Key)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "unfollow",
+        vec![Parameter::new(ARG_USER, CLType::Key)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "followers_page",
+        vec![
+            Parameter::new(ARG_USER, CLType::Key),
+            Parameter::new(ARG_START, CLType::U256),
+            Parameter::new(ARG_COUNT, CLType::U256),
+        ],
+        CLType::List(Box::new(CLType::Key)),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "following_page",
+        vec![
+            Parameter::new(ARG_USER, CLType::Key),
+            Parameter::new(ARG_START, CLType::U256),
+            Parameter::new(ARG_COUNT, CLType::U256),
+        ],
+        CLType::List(Box::new(CLType::Key)),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "is_mutual",
+        vec![
+            Parameter::new(ARG_USER, CLType::Key),
+            Parameter::new(ARG_TARGET, CLType::Key),
+        ],
+        CLType::Bool,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "rate_media",
+        vec![
+            Parameter::new(ARG_MEDIA_ID, CLType::String),
+            Parameter::new(ARG_RATING, CLType::U8),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "review_media",
+        vec![
+            Parameter::new(ARG_MEDIA_ID, CLType::String),
+            Parameter::new(ARG_REVIEW, CLType::String),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "token_review",
+        vec![Parameter::new(ARG_TOKEN_ID, CLType::U256)],
+        CLType::Tuple2([Box::new(CLType::U8), Box::new(CLType::String)]),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "media_rating",
+        vec![Parameter::new(ARG_MEDIA_ID, CLType::String)],
+        CLType::Tuple3([
+            Box::new(CLType::U64),
+            Box::new(CLType::U64),
+            Box::new(CLType::List(Box::new(CLType::U64))),
+        ]),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_achievement",
+        vec![
+            Parameter::new(ARG_ACHIEVEMENT_ID, CLType::String),
+            Parameter::new(ARG_RULE, CLType::U8),
+            Parameter::new(ARG_KIND, CLType::U8),
+            Parameter::new(ARG_THRESHOLD, CLType::U64),
+            Parameter::new(ARG_URI, CLType::String),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "remove_achievement",
+        vec![Parameter::new(ARG_ACHIEVEMENT_ID, CLType::String)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "user_achievements",
+        vec![Parameter::new(ARG_USER, CLType::Key)],
+        CLType::List(Box::new(CLType::Tuple2([Box::new(CLType::String), Box::new(CLType::U256)]))),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "token_info",
+        vec![Parameter::new(ARG_TOKEN_ID, CLType::U256)],
+        CLType::Tuple3([
+            Box::new(CLType::U64),
+            Box::new(CLType::U64),
+            Box::new(CLType::Bool),
+        ]),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_first_edition_limit",
+        vec![Parameter::new(ARG_FIRST_EDITION_LIMIT, CLType::U64)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "first_edition_limit",
+        vec![],
+        CLType::U64,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_metadata_policy",
+        vec![Parameter::new(ARG_POLICY, CLType::U8)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_token_metadata",
+        vec![
+            Parameter::new(ARG_TOKEN_ID, CLType::U256),
+            Parameter::new(ARG_PLATFORM, CLType::String),
+            Parameter::new(ARG_WATCH_TIME, CLType::U64),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "token_metadata",
+        vec![Parameter::new(ARG_TOKEN_ID, CLType::U256)],
+        CLType::String,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_kind_base_uri",
+        vec![
+            Parameter::new(ARG_KIND, CLType::U8),
+            Parameter::new(ARG_BASE_URI, CLType::String),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "kind_base_uri",
+        vec![Parameter::new(ARG_KIND, CLType::U8)],
+        CLType::String,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "revoke_completion",
+        vec![
+            Parameter::new(ARG_USER, CLType::Key),
+            Parameter::new(ARG_MEDIA_ID, CLType::String),
+            Parameter::new(ARG_REASON_CODE, CLType::U8),
+            Parameter::new(ARG_COOLDOWN, CLType::U64),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "revocation_info",
+        vec![
+            Parameter::new(ARG_USER, CLType::Key),
+            Parameter::new(ARG_MEDIA_ID, CLType::String),
+        ],
+        CLType::Tuple3([
+            Box::new(CLType::U8),
+            Box::new(CLType::U64),
+            Box::new(CLType::U64),
+        ]),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_repeat_completions",
+        vec![Parameter::new(ARG_ENABLED, CLType::Bool)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "completion_count_for",
+        vec![
+            Parameter::new(ARG_USER, CLType::Key),
+            Parameter::new(ARG_MEDIA_ID, CLType::String),
+        ],
+        CLType::U64,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "pause",
+        vec![Parameter::new(ARG_SCOPES, CLType::U8)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "unpause",
+        vec![Parameter::new(ARG_SCOPES, CLType::U8)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "paused_scopes",
+        vec![],
+        CLType::U8,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_mint_limits",
+        vec![
+            Parameter::new(ARG_WINDOW, CLType::U64),
+            Parameter::new(ARG_PER_USER_LIMIT, CLType::U64),
+            Parameter::new(ARG_PER_MINTER_LIMIT, CLType::U64),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "mint_limits",
+        vec![],
+        CLType::Tuple3([
+            Box::new(CLType::U64),
+            Box::new(CLType::U64),
+            Box::new(CLType::U64),
+        ]),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "supports_interface",
+        vec![Parameter::new(ARG_INTERFACE_ID, CLType::String)],
+        CLType::Bool,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "add_hook",
+        vec![
+            Parameter::new(ARG_HOOK, CLType::Key),
+            Parameter::new(ARG_CAN_VETO, CLType::Bool),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "remove_hook",
+        vec![Parameter::new(ARG_HOOK, CLType::Key)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "hooks",
+        vec![],
+        CLType::List(Box::new(CLType::Key)),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "hook_info",
+        vec![Parameter::new(ARG_HOOK, CLType::Key)],
+        CLType::Bool,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_hooks_enabled",
+        vec![Parameter::new(ARG_ENABLED, CLType::Bool)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "hooks_enabled",
+        vec![],
+        CLType::Bool,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_reward_token",
+        vec![Parameter::new(
+            ARG_REWARD_TOKEN,
+            CLType::Option(Box::new(CLType::Key)),
+        )],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "reward_token",
+        vec![],
+        CLType::Option(Box::new(CLType::Key)),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_kind_reward",
+        vec![
+            Parameter::new(ARG_KIND, CLType::U8),
+            Parameter::new(ARG_AMOUNT, CLType::U256),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "kind_reward",
+        vec![Parameter::new(ARG_KIND, CLType::U8)],
+        CLType::U256,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "fund_rewards",
+        vec![Parameter::new(ARG_AMOUNT, CLType::U256)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "withdraw_rewards",
+        vec![
+            Parameter::new(ARG_AMOUNT, CLType::U256),
+            Parameter::new(ARG_RECIPIENT, CLType::Key),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "total_rewards_paid",
+        vec![],
+        CLType::U256,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_paid_minting",
+        vec![Parameter::new(ARG_ENABLED, CLType::Bool)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_attestor",
+        vec![Parameter::new(ARG_ATTESTOR, CLType::PublicKey)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_mint_fee",
+        vec![
+            Parameter::new(ARG_KIND, CLType::U8),
+            Parameter::new(ARG_FEE, CLType::U512),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "mint_fee",
+        vec![Parameter::new(ARG_KIND, CLType::U8)],
+        CLType::U512,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "complete_with_payment",
+        vec![
+            Parameter::new(ARG_KIND, CLType::U8),
+            Parameter::new(ARG_URI, CLType::String),
+            Parameter::new(ARG_NAME, CLType::String),
+            Parameter::new(ARG_EXPIRES_AT, CLType::U64),
+            Parameter::new(ARG_SIGNATURE, CLType::List(Box::new(CLType::U8))),
+            Parameter::new(ARG_PURSE, CLType::URef),
+        ],
+        CLType::U256,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "withdraw_fees",
+        vec![
+            Parameter::new(ARG_AMOUNT, CLType::U512),
+            Parameter::new(ARG_TREASURY, CLType::Key),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "fee_balance",
+        vec![],
+        CLType::U512,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "create_campaign",
+        vec![
+            Parameter::new(ARG_MEDIA_ID, CLType::String),
+            Parameter::new(ARG_CAP, CLType::U64),
+            Parameter::new(ARG_START_TIME, CLType::U64),
+            Parameter::new(ARG_END_TIME, CLType::U64),
+            Parameter::new(ARG_EDITION_URI, CLType::String),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "campaign_info",
+        vec![Parameter::new(ARG_MEDIA_ID, CLType::String)],
+        CLType::Tuple3([
+            Box::new(CLType::Tuple3([
+                Box::new(CLType::Key),
+                Box::new(CLType::U64),
+                Box::new(CLType::U64),
+            ])),
+            Box::new(CLType::Tuple2([Box::new(CLType::U64), Box::new(CLType::U64)])),
+            Box::new(CLType::String),
+        ]),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "campaign_remaining",
+        vec![Parameter::new(ARG_MEDIA_ID, CLType::String)],
+        CLType::U64,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "create_challenge",
+        vec![
+            Parameter::new(ARG_CHALLENGE_ID, CLType::String),
+            Parameter::new(ARG_RULE, CLType::U8),
+            Parameter::new(ARG_KIND, CLType::U8),
+            Parameter::new(ARG_REQUIRED, CLType::U64),
+            Parameter::new(ARG_MEDIA_IDS, CLType::List(Box::new(CLType::String))),
+            Parameter::new(ARG_START_TIME, CLType::U64),
+            Parameter::new(ARG_END_TIME, CLType::U64),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "challenge_info",
+        vec![Parameter::new(ARG_CHALLENGE_ID, CLType::String)],
+        CLType::Tuple3([
+            Box::new(CLType::Tuple3([
+                Box::new(CLType::U8),
+                Box::new(CLType::U8),
+                Box::new(CLType::U64),
+            ])),
+            Box::new(CLType::Tuple2([Box::new(CLType::U64), Box::new(CLType::U64)])),
+            Box::new(CLType::List(Box::new(CLType::String))),
+        ]),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "join_challenge",
+        vec![Parameter::new(ARG_CHALLENGE_ID, CLType::String)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "challenge_progress",
+        vec![
+            Parameter::new(ARG_CHALLENGE_ID, CLType::String),
+            Parameter::new(ARG_USER, CLType::Key),
+        ],
+        CLType::Tuple3([
+            Box::new(CLType::U64),
+            Box::new(CLType::U64),
+            Box::new(CLType::Bool),
+        ]),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "challenge_leaderboard",
+        vec![Parameter::new(ARG_CHALLENGE_ID, CLType::String)],
+        CLType::List(Box::new(CLType::Tuple2([
+            Box::new(CLType::Key),
+            Box::new(CLType::U64),
+        ]))),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "user_stats",
+        vec![Parameter::new(ARG_USER, CLType::Key)],
+        CLType::Tuple3([
+            Box::new(CLType::Tuple3([
+                Box::new(CLType::U64),
+                Box::new(CLType::U64),
+                Box::new(CLType::U64),
+            ])),
+            Box::new(CLType::Map {
+                key: Box::new(CLType::U8),
+                value: Box::new(CLType::U64),
+            }),
+            Box::new(CLType::Tuple2([Box::new(CLType::U64), Box::new(CLType::U64)])),
+        ]),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_visibility",
+        vec![Parameter::new(ARG_VISIBILITY, CLType::U8)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "visibility",
+        vec![Parameter::new(ARG_USER, CLType::Key)],
+        CLType::U8,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "media_completers_page",
+        vec![
+            Parameter::new(ARG_MEDIA_ID, CLType::String),
+            Parameter::new(ARG_START, CLType::U256),
+            Parameter::new(ARG_COUNT, CLType::U256),
+        ],
+        CLType::List(Box::new(CLType::Key)),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "media_completer_count",
+        vec![Parameter::new(ARG_MEDIA_ID, CLType::String)],
+        CLType::U256,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "compute_commitment",
+        vec![
+            Parameter::new(ARG_USER, CLType::Key),
+            Parameter::new(ARG_MEDIA_ID, CLType::String),
+            Parameter::new(ARG_SALT, CLType::String),
+        ],
+        CLType::String,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "complete_private",
+        vec![Parameter::new(ARG_COMMITMENT, CLType::String)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "has_commitment",
+        vec![Parameter::new(ARG_COMMITMENT, CLType::String)],
+        CLType::Bool,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "prove_completion",
+        vec![
+            Parameter::new(ARG_USER, CLType::Key),
+            Parameter::new(ARG_MEDIA_ID, CLType::String),
+            Parameter::new(ARG_SALT, CLType::String),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    entry_points
+}
+
+fn get_uref(key: &str) -> URef {
+    runtime::get_key(key)
+        .and_then(Key::into_uref)
+        .unwrap_or_revert_with(ApiError::MissingKey)
+}
+
+fn get_or_init_uref<T: Default>(key: &str) -> URef
+where
+    T: casper_types::bytesrepr::FromBytes + casper_types::bytesrepr::ToBytes + casper_types::CLTyped,
+{
+    if let Some(uref) = runtime::get_key(key).and_then(Key::into_uref) {
+        return uref;
+    }
+    let uref = storage::new_uref(T::default());
+    runtime::put_key(key, uref.into());
+    uref
+}
+
+fn caller_field<T: CLTyped + FromBytes>(caller: &CallerInfo, index: u8) -> Option<T> {
+    caller
+        .get_field_by_index(index)
+        .and_then(|value| value.clone().into_t::<Option<T>>().ok())
+        .flatten()
+}
+
+fn key_from_caller(caller: CallerInfo) -> Option<Key> {
+    match caller.kind() {
+        CALLER_ACCOUNT => caller_field::<AccountHash>(&caller, CALLER_ACCOUNT).map(Key::Account),
+        CALLER_ENTITY => {
+            caller_field::<EntityAddr>(&caller, CALLER_ENTITY).map(Key::AddressableEntity)
+        }
+        CALLER_CONTRACT => caller_field::<ContractPackageHash>(&caller, CALLER_CONTRACT_PACKAGE)
+            .map(|package_hash| Key::Hash(package_hash.value())),
+        _ => None,
+    }
+}
+
+fn self_key() -> Key {
+    runtime::get_call_stack()
+        .into_iter()
+        .last()
+        .and_then(key_from_caller)
+        .unwrap_or_revert()
+}
+
+fn caller_key() -> Key {
+    runtime::get_immediate_caller()
+        .ok()
+        .and_then(key_from_caller)
+        .unwrap_or_else(|| Key::Account(runtime::get_caller()))
+}
+
+fn require_owner() {
+    let owner: AccountHash = storage::read(get_uref(OWNER_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_revert();
+    if caller_key() != Key::Account(owner) {
+        runtime::revert(ApiError::User(1));
+    }
+}
+
+fn get_backend() -> Key {
+    storage::read(get_uref(BACKEND_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_revert()
+}
+
+fn get_paused_scopes() -> u8 {
+    storage::read(get_or_init_uref::<u8>(PAUSED_SCOPES_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_paused_scopes(v: u8) {
+    storage::write(get_or_init_uref::<u8>(PAUSED_SCOPES_KEY), v);
+}
+
+fn require_not_paused(scope: u8) {
+    if get_paused_scopes() & scope != 0 {
+        runtime::revert(ApiError::User(39));
+    }
+}
+
+fn require_valid_scopes(scopes: u8) {
+    if scopes == 0 || scopes & !PAUSE_ALL_SCOPES != 0 {
+        runtime::revert(ApiError::User(38));
+    }
+}
+
+fn require_owner_or_backend() {
+    let owner: AccountHash = storage::read(get_uref(OWNER_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_revert();
+    let caller = caller_key();
+    if caller != Key::Account(owner) && caller != get_backend() {
+        runtime::revert(ApiError::User(35));
+    }
+}
+
+fn require_owner_or_registrar() {
+    let owner: AccountHash = storage::read(get_uref(OWNER_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_revert();
+    let caller = caller_key();
+    if caller == Key::Account(owner) {
+        return;
+    }
+    let registrars: BTreeMap<Key, bool> =
+        storage::read(get_or_init_uref::<BTreeMap<Key, bool>>(REGISTRAR_KEY))
+            .unwrap_or_revert_with(ApiError::Read)
+            .unwrap_or_default();
+    if !registrars.get(&caller).copied().unwrap_or(false) {
+        runtime::revert(ApiError::User(51));
+    }
+}
+
+fn require_backend() {
+    let caller = caller_key();
+    if caller != get_backend() {
+        runtime::revert(ApiError::User(2));
+    }
+}
+
+fn get_or_init_dictionary(name: &str) -> URef {
+    if let Some(uref) = runtime::get_key(name).and_then(Key::into_uref) {
+        return uref;
+    }
+    storage::new_dictionary(name).unwrap_or_revert()
+}
+
+fn get_next_token_id() -> U256 {
+    storage::read(get_uref(NEXT_TOKEN_ID_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or(U256::one())
+}
+
+fn set_next_token_id(v: U256) {
+    storage::write(get_uref(NEXT_TOKEN_ID_KEY), v);
+}
+
+fn get_media() -> BTreeMap<String, (u8, bool, (String, String))> {
+    storage::read(get_or_init_uref::<BTreeMap<String, (u8, bool, (String, String))>>(MEDIA_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_media(v: BTreeMap<String, (u8, bool, (String, String))>) {
+    storage::write(get_or_init_uref::<BTreeMap<String, (u8, bool, (String, String))>>(MEDIA_KEY), v);
+}
+
+fn get_token_owner() -> BTreeMap<U256, Key> {
+    storage::read(get_or_init_uref::<BTreeMap<U256, Key>>(TOKEN_OWNER_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_token_owner(v: BTreeMap<U256, Key>) {
+    storage::write(get_or_init_uref::<BTreeMap<U256, Key>>(TOKEN_OWNER_KEY), v);
+}
+
+fn get_token_media_id() -> BTreeMap<U256, String> {
+    storage::read(get_or_init_uref::<BTreeMap<U256, String>>(TOKEN_MEDIA_ID_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_token_media_id(v: BTreeMap<U256, String>) {
+    storage::write(get_or_init_uref::<BTreeMap<U256, String>>(TOKEN_MEDIA_ID_KEY), v);
+}
+
+fn get_completion_token_id() -> BTreeMap<(Key, String), U256> {
+    storage::read(get_or_init_uref::<BTreeMap<(Key, String), U256>>(COMPLETION_TOKEN_ID_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_completion_token_id(v: BTreeMap<(Key, String), U256>) {
+    storage::write(get_or_init_uref::<BTreeMap<(Key, String), U256>>(COMPLETION_TOKEN_ID_KEY), v);
+}
+
+fn get_user_token_ids() -> BTreeMap<Key, Vec<U256>> {
+    storage::read(get_or_init_uref::<BTreeMap<Key, Vec<U256>>>(USER_TOKEN_IDS_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_user_token_ids(v: BTreeMap<Key, Vec<U256>>) {
+    storage::write(get_or_init_uref::<BTreeMap<Key, Vec<U256>>>(USER_TOKEN_IDS_KEY), v);
+}
+
+fn get_user_token_index_plus_one() -> BTreeMap<(Key, U256), U256> {
+    storage::read(get_or_init_uref::<BTreeMap<(Key, U256), U256>>(USER_TOKEN_INDEX_PLUS_ONE_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_user_token_index_plus_one(v: BTreeMap<(Key, U256), U256>) {
+    storage::write(get_or_init_uref::<BTreeMap<(Key, U256), U256>>(USER_TOKEN_INDEX_PLUS_ONE_KEY), v);
+}
+
+fn get_media_completers() -> BTreeMap<String, Vec<Key>> {
+    storage::read(get_or_init_uref::<BTreeMap<String, Vec<Key>>>(MEDIA_COMPLETERS_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_media_completers(v: BTreeMap<String, Vec<Key>>) {
+    storage::write(get_or_init_uref::<BTreeMap<String, Vec<Key>>>(MEDIA_COMPLETERS_KEY), v);
+}
+
+fn get_media_completer_index_plus_one() -> BTreeMap<(String, Key), U256> {
+    storage::read(
+        get_or_init_uref::<BTreeMap<(String, Key), U256>>(MEDIA_COMPLETER_INDEX_PLUS_ONE_KEY),
+    )
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_media_completer_index_plus_one(v: BTreeMap<(String, Key), U256>) {
+    storage::write(
+        get_or_init_uref::<BTreeMap<(String, Key), U256>>(MEDIA_COMPLETER_INDEX_PLUS_ONE_KEY),
+        v,
+    );
+}
+
+fn get_group_members_map() -> BTreeMap<String, Vec<Key>> {
+    storage::read(get_or_init_uref::<BTreeMap<String, Vec<Key>>>(GROUP_MEMBERS_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_group_members_map(v: BTreeMap<String, Vec<Key>>) {
+    storage::write(get_or_init_uref::<BTreeMap<String, Vec<Key>>>(GROUP_MEMBERS_KEY), v);
+}
+
+fn get_group_index_plus_one() -> BTreeMap<(String, Key), U256> {
+    storage::read(get_or_init_uref::<BTreeMap<(String, Key), U256>>(GROUP_INDEX_PLUS_ONE_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_group_index_plus_one(v: BTreeMap<(String, Key), U256>) {
+    storage::write(get_or_init_uref::<BTreeMap<(String, Key), U256>>(GROUP_INDEX_PLUS_ONE_KEY), v);
+}
+
+fn get_rooms() -> BTreeMap<String, (String, Key, String)> {
+    storage::read(get_or_init_uref::<BTreeMap<String, (String, Key, String)>>(ROOMS_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_rooms(v: BTreeMap<String, (String, Key, String)>) {
+    storage::write(get_or_init_uref::<BTreeMap<String, (String, Key, String)>>(ROOMS_KEY), v);
+}
+
+fn get_media_rooms() -> BTreeMap<String, Vec<String>> {
+    storage::read(get_or_init_uref::<BTreeMap<String, Vec<String>>>(MEDIA_ROOMS_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_media_rooms(v: BTreeMap<String, Vec<String>>) {
+    storage::write(get_or_init_uref::<BTreeMap<String, Vec<String>>>(MEDIA_ROOMS_KEY), v);
+}
+
+fn get_group_policies() -> BTreeMap<String, u8> {
+    storage::read(get_or_init_uref::<BTreeMap<String, u8>>(GROUP_POLICY_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_group_policies(v: BTreeMap<String, u8>) {
+    storage::write(get_or_init_uref::<BTreeMap<String, u8>>(GROUP_POLICY_KEY), v);
+}
+
+type GroupRule = ((Key, String), (u8, u8, u64), Vec<String>);
+
+fn get_group_rules() -> BTreeMap<String, GroupRule> {
+    storage::read(get_or_init_uref::<BTreeMap<String, GroupRule>>(GROUP_RULES_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_group_rules(v: BTreeMap<String, GroupRule>) {
+    storage::write(get_or_init_uref::<BTreeMap<String, GroupRule>>(GROUP_RULES_KEY), v);
+}
+
+fn get_media_rule_groups() -> BTreeMap<String, Vec<String>> {
+    storage::read(get_or_init_uref::<BTreeMap<String, Vec<String>>>(MEDIA_RULE_GROUPS_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_media_rule_groups(v: BTreeMap<String, Vec<String>>) {
+    storage::write(get_or_init_uref::<BTreeMap<String, Vec<String>>>(MEDIA_RULE_GROUPS_KEY), v);
+}
+
+fn get_kind_rule_groups() -> BTreeMap<u8, Vec<String>> {
+    storage::read(get_or_init_uref::<BTreeMap<u8, Vec<String>>>(KIND_RULE_GROUPS_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_kind_rule_groups(v: BTreeMap<u8, Vec<String>>) {
+    storage::write(get_or_init_uref::<BTreeMap<u8, Vec<String>>>(KIND_RULE_GROUPS_KEY), v);
+}
+
+fn get_group_invites() -> BTreeMap<(String, Key), Key> {
+    storage::read(get_or_init_uref::<BTreeMap<(String, Key), Key>>(GROUP_INVITES_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_group_invites(v: BTreeMap<(String, Key), Key>) {
+    storage::write(get_or_init_uref::<BTreeMap<(String, Key), Key>>(GROUP_INVITES_KEY), v);
+}
+
+fn get_join_requests() -> BTreeMap<String, Vec<Key>> {
+    storage::read(get_or_init_uref::<BTreeMap<String, Vec<Key>>>(JOIN_REQUESTS_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_join_requests(v: BTreeMap<String, Vec<Key>>) {
+    storage::write(get_or_init_uref::<BTreeMap<String, Vec<Key>>>(JOIN_REQUESTS_KEY), v);
+}
+
+fn get_join_request_index_plus_one() -> BTreeMap<(String, Key), U256> {
+    storage::read(
+        get_or_init_uref::<BTreeMap<(String, Key), U256>>(JOIN_REQUEST_INDEX_PLUS_ONE_KEY),
+    )
+    .unwrap_or_revert_with(ApiError::Read)
+    .unwrap_or_default()
+}
+
+fn set_join_request_index_plus_one(v: BTreeMap<(String, Key), U256>) {
+    storage::write(
+        get_or_init_uref::<BTreeMap<(String, Key), U256>>(JOIN_REQUEST_INDEX_PLUS_ONE_KEY),
+        v,
+    );
+}
+
+fn get_messaging_prefs() -> BTreeMap<Key, u8> {
+    storage::read(get_or_init_uref::<BTreeMap<Key, u8>>(MESSAGING_PREFS_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_messaging_prefs(v: BTreeMap<Key, u8>) {
+    storage::write(get_or_init_uref::<BTreeMap<Key, u8>>(MESSAGING_PREFS_KEY), v);
+}
+
+fn get_blocks() -> BTreeMap<(Key, Key), bool> {
+    storage::read(get_or_init_uref::<BTreeMap<(Key, Key), bool>>(BLOCKS_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_blocks(v: BTreeMap<(Key, Key), bool>) {
+    storage::write(get_or_init_uref::<BTreeMap<(Key, Key), bool>>(BLOCKS_KEY), v);
+}
+
+fn get_followers() -> BTreeMap<Key, Vec<Key>> {
+    storage::read(get_or_init_uref::<BTreeMap<Key, Vec<Key>>>(FOLLOWERS_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_followers(v: BTreeMap<Key, Vec<Key>>) {
+    storage::write(get_or_init_uref::<BTreeMap<Key, Vec<Key>>>(FOLLOWERS_KEY), v);
+}
+
+fn get_follower_index_plus_one() -> BTreeMap<(Key, Key), U256> {
+    storage::read(get_or_init_uref::<BTreeMap<(Key, Key), U256>>(FOLLOWER_INDEX_PLUS_ONE_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_follower_index_plus_one(v: BTreeMap<(Key, Key), U256>) {
+    storage::write(get_or_init_uref::<BTreeMap<(Key, Key), U256>>(FOLLOWER_INDEX_PLUS_ONE_KEY), v);
+}
+
+fn get_following() -> BTreeMap<Key, Vec<Key>> {
+    storage::read(get_or_init_uref::<BTreeMap<Key, Vec<Key>>>(FOLLOWING_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_following(v: BTreeMap<Key, Vec<Key>>) {
+    storage::write(get_or_init_uref::<BTreeMap<Key, Vec<Key>>>(FOLLOWING_KEY), v);
+}
+
+fn get_following_index_plus_one() -> BTreeMap<(Key, Key), U256> {
+    storage::read(get_or_init_uref::<BTreeMap<(Key, Key), U256>>(FOLLOWING_INDEX_PLUS_ONE_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_following_index_plus_one(v: BTreeMap<(Key, Key), U256>) {
+    storage::write(get_or_init_uref::<BTreeMap<(Key, Key), U256>>(FOLLOWING_INDEX_PLUS_ONE_KEY), v);
+}
+
+fn get_follow_gated() -> bool {
+    storage::read(get_or_init_uref::<bool>(FOLLOW_GATED_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn get_token_ratings() -> BTreeMap<U256, u8> {
+    storage::read(get_or_init_uref::<BTreeMap<U256, u8>>(TOKEN_RATING_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_token_ratings(v: BTreeMap<U256, u8>) {
+    storage::write(get_or_init_uref::<BTreeMap<U256, u8>>(TOKEN_RATING_KEY), v);
+}
+
+fn get_token_reviews() -> BTreeMap<U256, String> {
+    storage::read(get_or_init_uref::<BTreeMap<U256, String>>(TOKEN_REVIEW_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_token_reviews(v: BTreeMap<U256, String>) {
+    storage::write(get_or_init_uref::<BTreeMap<U256, String>>(TOKEN_REVIEW_KEY), v);
+}
+
+fn get_media_ratings() -> BTreeMap<String, (u64, u64, Vec<u64>)> {
+    storage::read(get_or_init_uref::<BTreeMap<String, (u64, u64, Vec<u64>)>>(MEDIA_RATING_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_media_ratings(v: BTreeMap<String, (u64, u64, Vec<u64>)>) {
+    storage::write(get_or_init_uref::<BTreeMap<String, (u64, u64, Vec<u64>)>>(MEDIA_RATING_KEY), v);
+}
+
+fn get_achievements() -> BTreeMap<String, ((u8, u8, u64), String)> {
+    storage::read(get_or_init_uref::<BTreeMap<String, ((u8, u8, u64), String)>>(ACHIEVEMENTS_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_achievements(v: BTreeMap<String, ((u8, u8, u64), String)>) {
+    storage::write(get_or_init_uref::<BTreeMap<String, ((u8, u8, u64), String)>>(ACHIEVEMENTS_KEY), v);
+}
+
+fn get_user_achievements() -> BTreeMap<Key, Vec<(String, U256)>> {
+    storage::read(get_or_init_uref::<BTreeMap<Key, Vec<(String, U256)>>>(USER_ACHIEVEMENTS_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_user_achievements(v: BTreeMap<Key, Vec<(String, U256)>>) {
+    storage::write(get_or_init_uref::<BTreeMap<Key, Vec<(String, U256)>>>(USER_ACHIEVEMENTS_KEY), v);
+}
+
+fn get_achievement_awarded() -> BTreeMap<(Key, String), U256> {
+    storage::read(get_or_init_uref::<BTreeMap<(Key, String), U256>>(ACHIEVEMENT_AWARDED_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_achievement_awarded(v: BTreeMap<(Key, String), U256>) {
+    storage::write(get_or_init_uref::<BTreeMap<(Key, String), U256>>(ACHIEVEMENT_AWARDED_KEY), v);
+}
+
+fn achievement_token_id_base() -> U256 {
+    U256::one() << 128
+}
+
+fn next_achievement_token_id() -> U256 {
+    let uref = get_or_init_uref::<U256>(NEXT_ACHIEVEMENT_TOKEN_ID_KEY);
+    let counter = storage::read::<U256>(uref)
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+        + U256::one();
+    storage::write(uref, counter);
+    achievement_token_id_base() + counter
+}
+
+fn get_achievement_tokens() -> BTreeMap<U256, (String, String)> {
+    storage::read(get_or_init_uref::<BTreeMap<U256, (String, String)>>(ACHIEVEMENT_TOKENS_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_achievement_tokens(v: BTreeMap<U256, (String, String)>) {
+    storage::write(get_or_init_uref::<BTreeMap<U256, (String, String)>>(ACHIEVEMENT_TOKENS_KEY), v);
+}
+
+fn get_first_completers() -> BTreeMap<String, Key> {
+    storage::read(get_or_init_uref::<BTreeMap<String, Key>>(FIRST_COMPLETERS_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_first_completers(v: BTreeMap<String, Key>) {
+    storage::write(get_or_init_uref::<BTreeMap<String, Key>>(FIRST_COMPLETERS_KEY), v);
+}
+
+fn record_first_completer(user: Key, media_id: &str) -> bool {
+    let mut first_completers = get_first_completers();
+    if first_completers.contains_key(media_id) {
+        return false;
+    }
+    first_completers.insert(String::from(media_id), user);
+    set_first_completers(first_completers);
+    true
+}
+
+fn get_first_edition_limit() -> u64 {
+    storage::read(get_or_init_uref::<u64>(FIRST_EDITION_LIMIT_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn get_media_edition_counter() -> BTreeMap<String, u64> {
+    storage::read(get_or_init_uref::<BTreeMap<String, u64>>(MEDIA_EDITION_COUNTER_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_media_edition_counter(v: BTreeMap<String, u64>) {
+    storage::write(get_or_init_uref::<BTreeMap<String, u64>>(MEDIA_EDITION_COUNTER_KEY), v);
+}
+
+fn get_token_info_map() -> BTreeMap<U256, (u64, u64, bool)> {
+    storage::read(get_or_init_uref::<BTreeMap<U256, (u64, u64, bool)>>(TOKEN_INFO_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_token_info_map(v: BTreeMap<U256, (u64, u64, bool)>) {
+    storage::write(get_or_init_uref::<BTreeMap<U256, (u64, u64, bool)>>(TOKEN_INFO_KEY), v);
+}
+
+fn get_token_metadata_entry(token_id: U256) -> Option<(String, u64)> {
+    storage::dictionary_get(get_or_init_dictionary(TOKEN_METADATA_DICT), &format!("{}", token_id))
+        .unwrap_or_revert_with(ApiError::Read)
+}
+
+fn set_token_metadata_entry(token_id: U256, v: (String, u64)) {
+    storage::dictionary_put(get_or_init_dictionary(TOKEN_METADATA_DICT), &format!("{}", token_id), v);
+}
+
+fn get_metadata_policy() -> u8 {
+    storage::read(get_or_init_uref::<u8>(METADATA_POLICY_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or(METADATA_IMMUTABLE)
+}
+
+fn get_revocations() -> BTreeMap<(Key, String), (u8, u64, u64)> {
+    storage::read(get_or_init_uref::<BTreeMap<(Key, String), (u8, u64, u64)>>(REVOCATIONS_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_revocations(v: BTreeMap<(Key, String), (u8, u64, u64)>) {
+    storage::write(get_or_init_uref::<BTreeMap<(Key, String), (u8, u64, u64)>>(REVOCATIONS_KEY), v);
+}
+
+fn get_repeat_completions_allowed() -> bool {
+    storage::read(get_or_init_uref::<bool>(REPEAT_COMPLETIONS_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn get_completion_counts() -> BTreeMap<(Key, String), (u64, u64)> {
+    storage::read(get_or_init_uref::<BTreeMap<(Key, String), (u64, u64)>>(COMPLETION_COUNT_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_completion_counts(v: BTreeMap<(Key, String), (u64, u64)>) {
+    storage::write(get_or_init_uref::<BTreeMap<(Key, String), (u64, u64)>>(COMPLETION_COUNT_KEY), v);
+}
+
+fn get_mint_limits() -> (u64, u64, u64) {
+    storage::read(get_or_init_uref::<(u64, u64, u64)>(MINT_LIMITS_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn get_hooks() -> BTreeMap<Key, bool> {
+    storage::read(get_or_init_uref::<BTreeMap<Key, bool>>(HOOKS_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_hooks(v: BTreeMap<Key, bool>) {
+    storage::write(get_or_init_uref::<BTreeMap<Key, bool>>(HOOKS_KEY), v);
+}
+
+fn get_hooks_disabled() -> bool {
+    storage::read(get_or_init_uref::<bool>(HOOKS_DISABLED_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn get_reward_token() -> Option<Key> {
+    storage::read(get_or_init_uref::<Option<Key>>(REWARD_TOKEN_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn get_reward_table() -> BTreeMap<u8, U256> {
+    storage::read(get_or_init_uref::<BTreeMap<u8, U256>>(REWARD_TABLE_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_reward_table(v: BTreeMap<u8, U256>) {
+    storage::write(get_or_init_uref::<BTreeMap<u8, U256>>(REWARD_TABLE_KEY), v);
+}
+
+fn get_total_rewards_paid() -> U256 {
+    storage::read(get_or_init_uref::<U256>(TOTAL_REWARDS_PAID_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_total_rewards_paid(v: U256) {
+    storage::write(get_or_init_uref::<U256>(TOTAL_REWARDS_PAID_KEY), v);
+}
+
+fn get_rewarded_completions() -> BTreeMap<(Key, String), U256> {
+    storage::read(get_or_init_uref::<BTreeMap<(Key, String), U256>>(REWARDED_COMPLETIONS_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_rewarded_completions(v: BTreeMap<(Key, String), U256>) {
+    storage::write(get_or_init_uref::<BTreeMap<(Key, String), U256>>(REWARDED_COMPLETIONS_KEY), v);
+}
+
+fn get_paid_minting_enabled() -> bool {
+    storage::read(get_or_init_uref::<bool>(PAID_MINTING_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn get_attestor() -> Option<PublicKey> {
+    storage::read(get_or_init_uref::<Option<PublicKey>>(ATTESTOR_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn get_mint_fees() -> BTreeMap<u8, U512> {
+    storage::read(get_or_init_uref::<BTreeMap<u8, U512>>(MINT_FEES_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_mint_fees(v: BTreeMap<u8, U512>) {
+    storage::write(get_or_init_uref::<BTreeMap<u8, U512>>(MINT_FEES_KEY), v);
+}
+
+fn get_consumed_attestations() -> BTreeMap<String, u64> {
+    storage::read(get_or_init_uref::<BTreeMap<String, u64>>(CONSUMED_ATTESTATIONS_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_consumed_attestations(v: BTreeMap<String, u64>) {
+    storage::write(get_or_init_uref::<BTreeMap<String, u64>>(CONSUMED_ATTESTATIONS_KEY), v);
+}
+
+fn get_or_init_fee_purse() -> URef {
+    if let Some(purse) = runtime::get_key(FEE_PURSE_KEY).and_then(Key::into_uref) {
+        return purse;
+    }
+    let purse = system::create_purse();
+    runtime::put_key(FEE_PURSE_KEY, purse.into());
+    purse
+}
+
+type Campaign = ((Key, u64, u64), (u64, u64), String);
+
+fn get_campaigns() -> BTreeMap<String, Campaign> {
+    storage::read(get_or_init_uref::<BTreeMap<String, Campaign>>(CAMPAIGNS_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_campaigns(v: BTreeMap<String, Campaign>) {
+    storage::write(get_or_init_uref::<BTreeMap<String, Campaign>>(CAMPAIGNS_KEY), v);
+}
+
+fn get_token_campaign_uris() -> BTreeMap<U256, String> {
+    storage::read(get_or_init_uref::<BTreeMap<U256, String>>(TOKEN_CAMPAIGN_URI_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_token_campaign_uris(v: BTreeMap<U256, String>) {
+    storage::write(get_or_init_uref::<BTreeMap<U256, String>>(TOKEN_CAMPAIGN_URI_KEY), v);
+}
+
+type Challenge = ((u8, u8, u64), (u64, u64), Vec<String>);
+
+fn get_challenges() -> BTreeMap<String, Challenge> {
+    storage::read(get_or_init_uref::<BTreeMap<String, Challenge>>(CHALLENGES_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_challenges(v: BTreeMap<String, Challenge>) {
+    storage::write(get_or_init_uref::<BTreeMap<String, Challenge>>(CHALLENGES_KEY), v);
+}
+
+fn get_challenge_progress() -> BTreeMap<(String, Key), Vec<String>> {
+    storage::read(get_or_init_uref::<BTreeMap<(String, Key), Vec<String>>>(
+        CHALLENGE_PROGRESS_KEY,
+    ))
+    .unwrap_or_revert_with(ApiError::Read)
+    .unwrap_or_default()
+}
+
+fn set_challenge_progress(v: BTreeMap<(String, Key), Vec<String>>) {
+    storage::write(
+        get_or_init_uref::<BTreeMap<(String, Key), Vec<String>>>(CHALLENGE_PROGRESS_KEY),
+        v,
+    );
+}
+
+fn get_user_challenges() -> BTreeMap<Key, Vec<String>> {
+    storage::read(get_or_init_uref::<BTreeMap<Key, Vec<String>>>(USER_CHALLENGES_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_user_challenges(v: BTreeMap<Key, Vec<String>>) {
+    storage::write(get_or_init_uref::<BTreeMap<Key, Vec<String>>>(USER_CHALLENGES_KEY), v);
+}
+
+fn get_challenge_leaderboards() -> BTreeMap<String, Vec<(Key, u64, u64)>> {
+    storage::read(get_or_init_uref::<BTreeMap<String, Vec<(Key, u64, u64)>>>(
+        CHALLENGE_LEADERBOARD_KEY,
+    ))
+    .unwrap_or_revert_with(ApiError::Read)
+    .unwrap_or_default()
+}
+
+fn set_challenge_leaderboards(v: BTreeMap<String, Vec<(Key, u64, u64)>>) {
+    storage::write(
+        get_or_init_uref::<BTreeMap<String, Vec<(Key, u64, u64)>>>(CHALLENGE_LEADERBOARD_KEY),
+        v,
+    );
+}
+
+type UserStats = ((u64, u64, u64), BTreeMap<u8, u64>, (u64, u64));
+
+fn get_user_stats_map() -> BTreeMap<Key, UserStats> {
+    storage::read(get_or_init_uref::<BTreeMap<Key, UserStats>>(USER_STATS_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_user_stats_map(v: BTreeMap<Key, UserStats>) {
+    storage::write(get_or_init_uref::<BTreeMap<Key, UserStats>>(USER_STATS_KEY), v);
+}
+
+fn get_visibilities() -> BTreeMap<Key, u8> {
+    storage::read(get_or_init_uref::<BTreeMap<Key, u8>>(VISIBILITY_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_visibilities(v: BTreeMap<Key, u8>) {
+    storage::write(get_or_init_uref::<BTreeMap<Key, u8>>(VISIBILITY_KEY), v);
+}
+
+fn get_private_commitments() -> BTreeMap<String, (u64, Key)> {
+    storage::read(get_or_init_uref::<BTreeMap<String, (u64, Key)>>(PRIVATE_COMMITMENTS_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_private_commitments(v: BTreeMap<String, (u64, Key)>) {
+    storage::write(get_or_init_uref::<BTreeMap<String, (u64, Key)>>(PRIVATE_COMMITMENTS_KEY), v);
+}
+
+fn get_revealed_completions() -> BTreeMap<(Key, String), u64> {
+    storage::read(get_or_init_uref::<BTreeMap<(Key, String), u64>>(REVEALED_COMPLETIONS_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_revealed_completions(v: BTreeMap<(Key, String), u64>) {
+    storage::write(get_or_init_uref::<BTreeMap<(Key, String), u64>>(REVEALED_COMPLETIONS_KEY), v);
+}
+
+fn get_minter_windows() -> BTreeMap<Key, (u64, u64)> {
+    storage::read(get_or_init_uref::<BTreeMap<Key, (u64, u64)>>(MINTER_WINDOWS_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_minter_windows(v: BTreeMap<Key, (u64, u64)>) {
+    storage::write(get_or_init_uref::<BTreeMap<Key, (u64, u64)>>(MINTER_WINDOWS_KEY), v);
+}
+
+fn get_minter_user_windows() -> BTreeMap<(Key, Key), (u64, u64)> {
+    storage::read(get_or_init_uref::<BTreeMap<(Key, Key), (u64, u64)>>(MINTER_USER_WINDOWS_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_minter_user_windows(v: BTreeMap<(Key, Key), (u64, u64)>) {
+    storage::write(
+        get_or_init_uref::<BTreeMap<(Key, Key), (u64, u64)>>(MINTER_USER_WINDOWS_KEY),
+        v,
+    );
+}
+
+fn get_base_uri() -> String {
+    storage::read(get_uref(BASE_URI_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_base_uri_internal(v: String) {
+    storage::write(get_uref(BASE_URI_KEY), v);
+}
+
+fn get_kind_base_uris() -> BTreeMap<u8, String> {
+    storage::read(get_or_init_uref::<BTreeMap<u8, String>>(KIND_BASE_URI_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn set_kind_base_uris(v: BTreeMap<u8, String>) {
+    storage::write(get_or_init_uref::<BTreeMap<u8, String>>(KIND_BASE_URI_KEY), v);
+}
+
+fn media_id_hex(kind: u8, uri: &str, name: &str) -> String {
+    let mut bytes = Vec::new();
+    bytes.push(kind);
+    bytes.extend_from_slice(uri.as_bytes());
+    bytes.push(0);
+    bytes.extend_from_slice(name.as_bytes());
+    blake2b_hex(bytes)
+}
+
+fn room_id_hex(media_id: &str, name: &str) -> String {
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(media_id.as_bytes());
+    bytes.push(0);
+    bytes.extend_from_slice(name.as_bytes());
+    blake2b_hex(bytes)
+}
+
+fn group_id_hex(creator: Key, name: &str) -> String {
+    let mut bytes = creator.to_bytes().unwrap_or_revert();
+    bytes.push(0);
+    bytes.extend_from_slice(name.as_bytes());
+    blake2b_hex(bytes)
+}
+
+fn commitment_hex(user: Key, media_id: &str, salt: &str) -> String {
+    let mut bytes = user.to_bytes().unwrap_or_revert();
+    bytes.push(0);
+    bytes.extend_from_slice(media_id.as_bytes());
+    bytes.push(0);
+    bytes.extend_from_slice(salt.as_bytes());
+    blake2b_hex(bytes)
+}
+
+fn blake2b_hex(bytes: Vec<u8>) -> String {
+    let digest = runtime::blake2b(bytes);
+    let mut out = String::with_capacity(64);
+    for b in digest.iter() {
+        out.push_str(&hex_byte(*b));
+    }
+    out
+}
+
+fn resolve_uri_template(template: &str, media_id: &str, token_id: U256, kind: u8) -> String {
+    if !template.contains('{') {
+        return format!("{}{}", template, media_id);
+    }
+    template
+        .replace("{media_id}", media_id)
+        .replace("{token_id}", &format!("{}", token_id))
+        .replace("{kind}", &format!("{}", kind))
+}
+
+fn kind_label(kind: u8) -> &'static str {
+    match kind {
+        1 => "Movie",
+        2 => "Anime",
+        3 => "Comic",
+        4 => "Book",
+        5 => "Manga",
+        6 => "Show",
+        _ => "Media",
+    }
+}
+
+fn date_from_millis(millis: u64) -> String {
+    let days = (millis / 86_400_000) as i64;
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+fn xml_escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] =
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b0 = chunk[0] as u32;
+        let b1 = chunk.get(1).copied().unwrap_or(0) as u32;
+        let b2 = chunk.get(2).copied().unwrap_or(0) as u32;
+        let n = (b0 << 16) | (b1 << 8) | b2;
+        out.push(ALPHABET[(n >> 18) as usize & 0x3f] as char);
+        out.push(ALPHABET[(n >> 12) as usize & 0x3f] as char);
+        if chunk.len() > 1 {
+            out.push(ALPHABET[(n >> 6) as usize & 0x3f] as char);
+        } else {
+            out.push('=');
+        }
+        if chunk.len() > 2 {
+            out.push(ALPHABET[n as usize & 0x3f] as char);
+        } else {
+            out.push('=');
+        }
+    }
+    out
+}
+
+fn render_svg_badge(name: &str, kind: u8, completed_at: u64, edition: u64) -> String {
+    let mut title: String = name.chars().take(SVG_TITLE_MAX_CHARS).collect();
+    if name.chars().count() > SVG_TITLE_MAX_CHARS {
+        title.push_str("...");
+    }
+    let label = kind_label(kind);
+    let icon = label.chars().next().unwrap_or('M');
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"350\" height=\"350\" viewBox=\"0 0 350 350\">\
+<rect width=\"350\" height=\"350\" rx=\"24\" fill=\"#111827\"/>\
+<circle cx=\"175\" cy=\"110\" r=\"48\" fill=\"#6366f1\"/>\
+<text x=\"175\" y=\"127\" font-family=\"sans-serif\" font-size=\"48\" fill=\"#ffffff\" text-anchor=\"middle\">{}</text>\
+<text x=\"175\" y=\"205\" font-family=\"sans-serif\" font-size=\"20\" fill=\"#ffffff\" text-anchor=\"middle\">{}</text>\
+<text x=\"175\" y=\"237\" font-family=\"sans-serif\" font-size=\"14\" fill=\"#9ca3af\" text-anchor=\"middle\">{} - completed {}</text>\
+<text x=\"175\" y=\"295\" font-family=\"sans-serif\" font-size=\"16\" fill=\"#fbbf24\" text-anchor=\"middle\">Edition #{}</text>\
+</svg>",
+        icon,
+        xml_escape(&title),
+        label,
+        date_from_millis(completed_at),
+        edition,
+    )
+}
+
+fn json_escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                out.push_str("\\u00");
+                out.push_str(&hex_byte(c as u8));
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn hex_byte(b: u8) -> String {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+    let mut s = String::with_capacity(2);
+    s.push(HEX[(b >> 4) as usize] as char);
+    s.push(HEX[(b & 0x0f) as usize] as char);
+    s
+}
+
+fn page_of<T: Clone>(list: &[T], start: U256, count: U256) -> Vec<T> {
+    let len = U256::from(list.len() as u64);
+    let start = start.min(len).as_u64() as usize;
+    let count = count.min(len).as_u64() as usize;
+    list.iter().skip(start).take(count).cloned().collect()
+}
+
+fn visible_keys<F: Fn(Key) -> bool>(keys: &[Key], viewer: Key, shares_with: F) -> Vec<Key> {
+    let visibilities = get_visibilities();
+    keys.iter()
+        .filter(|candidate| {
+            if **candidate == viewer {
+                return true;
+            }
+            match visibilities
+                .get(*candidate)
+                .copied()
+                .unwrap_or(VISIBILITY_PUBLIC)
+            {
+                VISIBILITY_PUBLIC => true,
+                VISIBILITY_CO_COMPLETERS => shares_with(**candidate),
+                _ => false,
+            }
+        })
+        .copied()
+        .collect()
+}
+
+fn visible_completers(completers: &[Key], viewer: Key, viewer_completed: bool) -> Vec<Key> {
+    visible_keys(completers, viewer, |_| viewer_completed)
+}
+
+fn visible_group_members(group_id: &str, viewer: Key) -> Vec<Key> {
+    let list = get_group_members_map()
+        .get(group_id)
+        .cloned()
+        .unwrap_or_default();
+    let media_id = match get_rooms().get(group_id) {
+        Some((media_id, _creator, _name)) => media_id.clone(),
+        None => String::from(group_id),
+    };
+    let viewer_shares =
+        is_group_member_internal(viewer, group_id) || is_completed(viewer, media_id);
+    visible_completers(&list, viewer, viewer_shares)
+}
+
+fn completed_media_by_user() -> BTreeMap<Key, BTreeSet<String>> {
+    let mut by_user = BTreeMap::<Key, BTreeSet<String>>::new();
+    let completed = get_completion_token_id()
+        .into_keys()
+        .chain(get_revealed_completions().into_keys());
+    for (user, media_id) in completed {
+        by_user.entry(user).or_default().insert(media_id);
+    }
+    by_user
+}
+
+fn visible_social_keys(keys: &[Key], viewer: Key) -> Vec<Key> {
+    let by_user = completed_media_by_user();
+    let viewer_media = match by_user.get(&viewer) {
+        Some(v) => v,
+        None => return visible_keys(keys, viewer, |_| false),
+    };
+    visible_keys(keys, viewer, |candidate| {
+        by_user
+            .get(&candidate)
+            .is_some_and(|media| !viewer_media.is_disjoint(media))
+    })
+}
+
+fn is_completed(user: Key, media_id: String) -> bool {
+    let key = (user, media_id);
+    get_completion_token_id()
+        .get(&key)
+        .copied()
+        .unwrap_or(U256::zero())
+        != U256::zero()
+        || get_revealed_completions().contains_key(&key)
+}
+
+fn group_join_policy(group_id: &str) -> u8 {
+    get_group_policies()
+        .get(group_id)
+        .copied()
+        .unwrap_or(JOIN_POLICY_OPEN)
+}
+
+fn require_valid_join_policy(policy: u8) {
+    if policy != JOIN_POLICY_OPEN
+        && policy != JOIN_POLICY_INVITE_ONLY
+        && policy != JOIN_POLICY_APPROVAL
+    {
+        runtime::revert(ApiError::User(10));
+    }
+}
+
+fn require_valid_rule(rule: (u8, u8, u64), media_ids: &[String]) {
+    let (rule, _kind, required) = rule;
+    let valid = match rule {
+        RULE_ALL_OF => !media_ids.is_empty(),
+        RULE_ANY_OF => required > 0 && required <= media_ids.len() as u64,
+        RULE_KIND_COUNT => required > 0,
+        _ => false,
+    };
+    if !valid {
+        runtime::revert(ApiError::User(14));
+    }
+}
+
+fn completed_count_of_kind(user: Key, kind: u8) -> u64 {
+    let user_tokens = get_user_token_ids();
+    let token_media = get_token_media_id();
+    let media = get_media();
+    user_tokens
+        .get(&user)
+        .map(|tokens| {
+            tokens
+                .iter()
+                .filter(|token_id| {
+                    token_media
+                        .get(token_id)
+                        .and_then(|media_id| media.get(media_id))
+                        .map(|v| v.0 == kind)
+                        .unwrap_or(false)
+                })
+                .count() as u64
+        })
+        .unwrap_or(0)
+}
+
+fn rule_satisfied(user: Key, rule: (u8, u8, u64), media_ids: &[String]) -> bool {
+    let (rule, kind, required) = rule;
+    let completion = get_completion_token_id();
+    let revealed = get_revealed_completions();
+    let completed = |media_id: &String| {
+        let key = (user, media_id.clone());
+        completion.get(&key).copied().unwrap_or(U256::zero()) != U256::zero()
+            || revealed.contains_key(&key)
+    };
+    match rule {
+        RULE_ALL_OF => media_ids.iter().all(completed),
+        RULE_ANY_OF => media_ids.iter().filter(|m| completed(m)).count() as u64 >= required,
+        RULE_KIND_COUNT => completed_count_of_kind(user, kind) >= required,
+        _ => false,
+    }
+}
+
+fn challenge_goal(rule: (u8, u8, u64), media_ids: &[String]) -> u64 {
+    match rule.0 {
+        RULE_ALL_OF => media_ids.len() as u64,
+        _ => rule.2,
+    }
+}
+
+fn challenge_counts_media(
+    rule: (u8, u8, u64),
+    media_ids: &[String],
+    media_id: &String,
+    kind: u8,
+) -> bool {
+    match rule.0 {
+        RULE_ALL_OF | RULE_ANY_OF => media_ids.contains(media_id),
+        RULE_KIND_COUNT => kind == rule.1,
+        _ => false,
+    }
+}
+
+fn update_challenge_leaderboard(challenge_id: String, user: Key, progress: u64, now: u64) {
+    let mut leaderboards = get_challenge_leaderboards();
+    let board = leaderboards.entry(challenge_id).or_default();
+    board.retain(|(entry, _progress, _reached_at)| *entry != user);
+    board.push((user, progress, now));
+    board.sort_by(|a, b| b.1.cmp(&a.1).then(a.2.cmp(&b.2)));
+    board.truncate(LEADERBOARD_SIZE);
+    set_challenge_leaderboards(leaderboards);
+}
+
+fn record_challenge_progress_internal(user: Key, media_id: String, now: u64) {
+    let joined = get_user_challenges().get(&user).cloned().unwrap_or_default();
+    if joined.is_empty() {
+        return;
+    }
+    let kind = get_media().get(&media_id).map(|v| v.0).unwrap_or(0);
+    let challenges = get_challenges();
+    let mut progress = get_challenge_progress();
+    let mut changed = false;
+    for challenge_id in joined.iter() {
+        let (rule, (start, end), media_ids) = match challenges.get(challenge_id) {
+            Some(v) => v,
+            None => continue,
+        };
+        if now < *start || now >= *end || !challenge_counts_media(*rule, media_ids, &media_id, kind)
+        {
+            continue;
+        }
+        let counted = progress.entry((challenge_id.clone(), user)).or_default();
+        if counted.contains(&media_id) {
+            continue;
+        }
+        counted.push(media_id.clone());
+        let count = counted.len() as u64;
+        update_challenge_leaderboard(challenge_id.clone(), user, count, now);
+        changed = true;
+    }
+    if changed {
+        set_challenge_progress(progress);
+    }
+}
+
+fn rebuild_challenge_leaderboard(
+    challenge_id: &String,
+    progress: &BTreeMap<(String, Key), Vec<String>>,
+    now: u64,
+) {
+    let mut leaderboards = get_challenge_leaderboards();
+    let previous = leaderboards.get(challenge_id).cloned().unwrap_or_default();
+    let mut board: Vec<(Key, u64, u64)> = progress
+        .iter()
+        .filter(|((id, _user), counted)| id == challenge_id && !counted.is_empty())
+        .map(|((_id, user), counted)| {
+            let count = counted.len() as u64;
+            let reached_at = previous
+                .iter()
+                .find(|(entry, progress, _reached_at)| entry == user && *progress == count)
+                .map(|(_entry, _progress, reached_at)| *reached_at)
+                .unwrap_or(now);
+            (*user, count, reached_at)
+        })
+        .collect();
+    board.sort_by(|a, b| b.1.cmp(&a.1).then(a.2.cmp(&b.2)));
+    board.truncate(LEADERBOARD_SIZE);
+    leaderboards.insert(challenge_id.clone(), board);
+    set_challenge_leaderboards(leaderboards);
+}
+
+fn revoke_challenge_progress_internal(user: Key, media_id: &String, now: u64) {
+    let joined = get_user_challenges().get(&user).cloned().unwrap_or_default();
+    let mut progress = get_challenge_progress();
+    let mut affected = Vec::new();
+    for challenge_id in joined.iter() {
+        if let Some(counted) = progress.get_mut(&(challenge_id.clone(), user)) {
+            if counted.contains(media_id) {
+                counted.retain(|id| id != media_id);
+                affected.push(challenge_id.clone());
+            }
+        }
+    }
+    if affected.is_empty() {
+        return;
+    }
+    for challenge_id in affected.iter() {
+        rebuild_challenge_leaderboard(challenge_id, &progress, now);
+    }
+    set_challenge_progress(progress);
+}
+
+fn is_eligible_for_group(user: Key, group_id: &str) -> bool {
+    if let Some((media_id, _creator, _name)) = get_rooms().get(group_id) {
+        return is_completed(user, media_id.clone());
+    }
+    if let Some((_meta, rule, media_ids)) = get_group_rules().get(group_id) {
+        return rule_satisfied(user, *rule, media_ids);
+    }
+    is_completed(user, String::from(group_id))
+}
+
+fn group_admin_of(group_id: &str) -> Option<Key> {
+    match get_rooms().get(group_id) {
+        Some((_media_id, creator, _name)) => Some(*creator),
+        None => get_group_rules()
+            .get(group_id)
+            .map(|((creator, _name), _rule, _media_ids)| *creator),
+    }
+}
+
+fn is_group_admin(group_id: &str, key: Key) -> bool {
+    match group_admin_of(group_id) {
+        Some(creator) => creator == key,
+        None => {
+            let owner: AccountHash = storage::read(get_uref(OWNER_KEY))
+                .unwrap_or_revert_with(ApiError::Read)
+                .unwrap_or_revert();
+            key == Key::Account(owner)
+        }
+    }
+}
+
+fn require_group_admin(group_id: &str, caller: Key) {
+    match group_admin_of(group_id) {
+        Some(creator) if creator == caller => {}
+        Some(_) => runtime::revert(ApiError::User(17)),
+        None => require_owner(),
+    }
+}
+
+fn is_group_member_internal(user: Key, group_id: &str) -> bool {
+    get_group_index_plus_one()
+        .get(&(String::from(group_id), user))
+        .copied()
+        .unwrap_or(U256::zero())
+        != U256::zero()
+}
+
+fn has_join_request(user: Key, group_id: &str) -> bool {
+    get_join_request_index_plus_one()
+        .get(&(String::from(group_id), user))
+        .copied()
+        .unwrap_or(U256::zero())
+        != U256::zero()
+}
+
+fn is_blocked_internal(user: Key, target: Key) -> bool {
+    get_blocks().get(&(user, target)).copied().unwrap_or(false)
+}
+
+fn is_following(follower: Key, followee: Key) -> bool {
+    get_following_index_plus_one()
+        .get(&(follower, followee))
+        .copied()
+        .unwrap_or(U256::zero())
+        != U256::zero()
+}
+
+fn messaging_preference_of(user: Key) -> u8 {
+    get_messaging_prefs()
+        .get(&user)
+        .copied()
+        .unwrap_or(MESSAGING_CO_COMPLETERS)
+}
+
+fn shares_any_completion(a: Key, b: Key) -> bool {
+    let user_tokens = get_user_token_ids();
+    let token_media = get_token_media_id();
+    let completion = get_completion_token_id();
+    user_tokens
+        .get(&a)
+        .map(|tokens| {
+            tokens.iter().any(|token_id| {
+                token_media
+                    .get(token_id)
+                    .map(|media_id| completion.contains_key(&(b, media_id.clone())))
+                    .unwrap_or(false)
+            })
+        })
+        .unwrap_or(false)
+}
+
+fn shares_listed_completion(a: Key, b: Key) -> bool {
+    let user_tokens = get_user_token_ids();
+    let token_media = get_token_media_id();
+    let completer_index = get_media_completer_index_plus_one();
+    user_tokens
+        .get(&a)
+        .map(|tokens| {
+            tokens.iter().any(|token_id| {
+                token_media
+                    .get(token_id)
+                    .map(|media_id| {
+                        completer_index
+                            .get(&(media_id.clone(), b))
+                            .copied()
+                            .unwrap_or(U256::zero())
+                            != U256::zero()
+                    })
+                    .unwrap_or(false)
+            })
+        })
+        .unwrap_or(false)
+}
+
+fn dm_allowed(from: Key, to: Key, shares_completion: bool) -> bool {
+    if is_blocked_internal(to, from) || is_blocked_internal(from, to) {
+        return false;
+    }
+    match messaging_preference_of(to) {
+        MESSAGING_NOBODY => false,
+        MESSAGING_MUTUAL_FOLLOWS => is_following(from, to) && is_following(to, from),
+        _ => shares_completion,
+    }
+}
+
+fn completion_token_of(user: Key, media_id: String) -> U256 {
+    let token_id = get_completion_token_id()
+        .get(&(user, media_id))
+        .copied()
+        .unwrap_or(U256::zero());
+    if token_id == U256::zero() {
+        runtime::revert(ApiError::User(29));
+    }
+    token_id
+}
+
+fn require_token_owner(token_id: U256, user: Key) {
+    let token_owner = get_token_owner();
+    match token_owner.get(&token_id) {
+        Some(owner) if *owner == user => {}
+        _ => runtime::revert(ApiError::User(3)),
+    }
+}
+
+fn add_media_completer(user: Key, media_id: String) {
+    let mut media_completers = get_media_completers();
+    let mut index_map = get_media_completer_index_plus_one();
+
+    let key = (media_id.clone(), user);
+    if index_map.get(&key).copied().unwrap_or(U256::zero()) != U256::zero() {
+        return;
+    }
+
+    let list = media_completers.entry(media_id.clone()).or_insert_with(Vec::new);
+    list.push(user);
+    index_map.insert(key, U256::from(list.len() as u64));
+
+    set_media_completers(media_completers);
+    set_media_completer_index_plus_one(index_map);
+}
+
+fn remove_media_completer(user: Key, media_id: String) {
+    let mut media_completers = get_media_completers();
+    let mut index_map = get_media_completer_index_plus_one();
+
+    let key = (media_id.clone(), user);
+    let index_plus_one = index_map.get(&key).copied().unwrap_or(U256::zero());
+    if index_plus_one == U256::zero() {
+        return;
+    }
+
+    let list = media_completers.entry(media_id.clone()).or_insert_with(Vec::new);
+    let idx = (index_plus_one.as_u64() as usize).saturating_sub(1);
+    let last_idx = list.len().saturating_sub(1);
+
+    if idx < list.len() {
+        if idx != last_idx {
+            let last = list[last_idx];
+            list[idx] = last;
+            index_map.insert((media_id.clone(), last), U256::from(idx as u64 + 1));
+        }
+        list.pop();
+    }
+
+    index_map.remove(&key);
+
+    set_media_completers(media_completers);
+    set_media_completer_index_plus_one(index_map);
+}
+
+fn join_group_internal(user: Key, media_id: String) {
+    let mut group_members = get_group_members_map();
+    let mut group_index = get_group_index_plus_one();
+
+    let key = (media_id.clone(), user);
+    if group_index.get(&key).copied().unwrap_or(U256::zero()) != U256::zero() {
+        return;
+    }
+
+    let list = group_members.entry(media_id.clone()).or_insert_with(Vec::new);
+    list.push(user);
+    group_index.insert(key, U256::from(list.len() as u64));
+
+    set_group_members_map(group_members);
+    set_group_index_plus_one(group_index);
+    record_group_stats(user, true);
+}
+
+fn remove_group_member_internal(user: Key, media_id: String) {
+    let mut group_members = get_group_members_map();
+    let mut group_index = get_group_index_plus_one();
+
+    let key = (media_id.clone(), user);
+    let index_plus_one = group_index.get(&key).copied().unwrap_or(U256::zero());
+    if index_plus_one == U256::zero() {
+        return;
+    }
+
+    let list = group_members.entry(media_id.clone()).or_insert_with(Vec::new);
+    let idx = (index_plus_one.as_u64() as usize).saturating_sub(1);
+    let last_idx = list.len().saturating_sub(1);
+
+    if idx < list.len() {
+        if idx != last_idx {
+            let last = list[last_idx];
+            list[idx] = last;
+            group_index.insert((media_id.clone(), last), U256::from(idx as u64 + 1));
+        }
+        list.pop();
+    }
+
+    group_index.remove(&key);
+
+    set_group_members_map(group_members);
+    set_group_index_plus_one(group_index);
+    record_group_stats(user, false);
+}
+
+fn leave_media_rooms_internal(user: Key, media_id: String) {
+    let media_rooms = get_media_rooms();
+    if let Some(rooms) = media_rooms.get(&media_id) {
+        for room_id in rooms.iter() {
+            remove_group_member_internal(user, room_id.clone());
+        }
+    }
+}
+
+fn index_rule_group(group_id: &str, rule: (u8, u8, u64), media_ids: &[String]) {
+    let (rule, kind, _required) = rule;
+    if rule == RULE_KIND_COUNT {
+        let mut kind_groups = get_kind_rule_groups();
+        kind_groups.entry(kind).or_default().push(String::from(group_id));
+        set_kind_rule_groups(kind_groups);
+        return;
+    }
+    let mut media_groups = get_media_rule_groups();
+    for media_id in media_ids.iter() {
+        let list = media_groups.entry(media_id.clone()).or_default();
+        if !list.iter().any(|id| id == group_id) {
+            list.push(String::from(group_id));
+        }
+    }
+    set_media_rule_groups(media_groups);
+}
+
+fn prune_rule_groups_internal(user: Key, media_id: &str) {
+    let mut group_ids = get_media_rule_groups()
+        .get(media_id)
+        .cloned()
+        .unwrap_or_default();
+    if let Some(kind) = get_media().get(media_id).map(|v| v.0) {
+        group_ids.extend(get_kind_rule_groups().get(&kind).cloned().unwrap_or_default());
+    }
+    if group_ids.is_empty() {
+        return;
+    }
+
+    let rules = get_group_rules();
+    let group_index = get_group_index_plus_one();
+    for group_id in group_ids.iter() {
+        let in_group = group_index
+            .get(&(group_id.clone(), user))
+            .copied()
+            .unwrap_or(U256::zero())
+            != U256::zero();
+        if !in_group {
+            continue;
+        }
+        if let Some((_meta, rule, media_ids)) = rules.get(group_id) {
+            if !rule_satisfied(user, *rule, media_ids) {
+                remove_group_member_internal(user, group_id.clone());
+            }
+        }
+    }
+}
+
+fn add_join_request_internal(user: Key, group_id: String) {
+    let mut requests = get_join_requests();
+    let mut request_index = get_join_request_index_plus_one();
+
+    let key = (group_id.clone(), user);
+    if request_index.get(&key).copied().unwrap_or(U256::zero()) != U256::zero() {
+        return;
+    }
+
+    let list = requests.entry(group_id.clone()).or_default();
+    list.push(user);
+    request_index.insert(key, U256::from(list.len() as u64));
+
+    set_join_requests(requests);
+    set_join_request_index_plus_one(request_index);
+}
+
+fn remove_join_request_internal(user: Key, group_id: String) {
+    let mut requests = get_join_requests();
+    let mut request_index = get_join_request_index_plus_one();
+
+    let key = (group_id.clone(), user);
+    let index_plus_one = request_index.get(&key).copied().unwrap_or(U256::zero());
+    if index_plus_one == U256::zero() {
+        return;
+    }
+
+    let list = requests.entry(group_id.clone()).or_default();
+    let idx = (index_plus_one.as_u64() as usize).saturating_sub(1);
+    let last_idx = list.len().saturating_sub(1);
+
+    if idx < list.len() {
+        if idx != last_idx {
+            let last = list[last_idx];
+            list[idx] = last;
+            request_index.insert((group_id.clone(), last), U256::from(idx as u64 + 1));
+        }
+        list.pop();
+    }
+
+    request_index.remove(&key);
+
+    set_join_requests(requests);
+    set_join_request_index_plus_one(request_index);
+}
+
+fn add_follow_internal(follower: Key, followee: Key) {
+    let mut following = get_following();
+    let mut following_index = get_following_index_plus_one();
+    let key = (follower, followee);
+    if following_index.get(&key).copied().unwrap_or(U256::zero()) != U256::zero() {
+        return;
+    }
+    let list = following.entry(follower).or_default();
+    list.push(followee);
+    following_index.insert(key, U256::from(list.len() as u64));
+    set_following(following);
+    set_following_index_plus_one(following_index);
+
+    let mut followers = get_followers();
+    let mut follower_index = get_follower_index_plus_one();
+    let list = followers.entry(followee).or_default();
+    list.push(follower);
+    follower_index.insert((followee, follower), U256::from(list.len() as u64));
+    set_followers(followers);
+    set_follower_index_plus_one(follower_index);
+}
+
+fn remove_follow_internal(follower: Key, followee: Key) {
+    let mut following = get_following();
+    let mut following_index = get_following_index_plus_one();
+
+    let key = (follower, followee);
+    let index_plus_one = following_index.get(&key).copied().unwrap_or(U256::zero());
+    if index_plus_one == U256::zero() {
+        return;
+    }
+
+    let list = following.entry(follower).or_default();
+    let idx = (index_plus_one.as_u64() as usize).saturating_sub(1);
+    let last_idx = list.len().saturating_sub(1);
+    if idx < list.len() {
+        if idx != last_idx {
+            let last = list[last_idx];
+            list[idx] = last;
+            following_index.insert((follower, last), U256::from(idx as u64 + 1));
+        }
+        list.pop();
+    }
+    following_index.remove(&key);
+    set_following(following);
+    set_following_index_plus_one(following_index);
+
+    let mut followers = get_followers();
+    let mut follower_index = get_follower_index_plus_one();
+
+    let key = (followee, follower);
+    let index_plus_one = follower_index.get(&key).copied().unwrap_or(U256::zero());
+    let list = followers.entry(followee).or_default();
+    let idx = (index_plus_one.as_u64() as usize).saturating_sub(1);
+    let last_idx = list.len().saturating_sub(1);
+    if index_plus_one != U256::zero() && idx < list.len() {
+        if idx != last_idx {
+            let last = list[last_idx];
+            list[idx] = last;
+            follower_index.insert((followee, last), U256::from(idx as u64 + 1));
+        }
+        list.pop();
+    }
+    follower_index.remove(&key);
+    set_followers(followers);
+    set_follower_index_plus_one(follower_index);
+}
+
+fn update_media_rating(media_id: String, old: Option<u8>, new: Option<u8>) {
+    let mut ratings = get_media_ratings();
+    let entry = ratings
+        .entry(media_id)
+        .or_insert_with(|| (0u64, 0u64, vec![0u64; RATING_MAX as usize]));
+    if let Some(old) = old {
+        entry.0 = entry.0.saturating_sub(old as u64);
+        entry.1 = entry.1.saturating_sub(1);
+        let bucket = &mut entry.2[(old - RATING_MIN) as usize];
+        *bucket = bucket.saturating_sub(1);
+    }
+    if let Some(new) = new {
+        entry.0 += new as u64;
+        entry.1 += 1;
+        entry.2[(new - RATING_MIN) as usize] += 1;
+    }
+    set_media_ratings(ratings);
+}
+
+fn clear_token_rating_internal(token_id: U256, media_id: String) {
+    let mut ratings = get_token_ratings();
+    if let Some(old) = ratings.remove(&token_id) {
+        set_token_ratings(ratings);
+        update_media_rating(media_id, Some(old), None);
+    }
+    let mut reviews = get_token_reviews();
+    if reviews.remove(&token_id).is_some() {
+        set_token_reviews(reviews);
+    }
+}
+
+fn remove_user_token_id(user: Key, token_id: U256) {
+    let mut user_token_ids = get_user_token_ids();
+    let mut index_map = get_user_token_index_plus_one();
+
+    let key = (user, token_id);
+    let index_plus_one = index_map.get(&key).copied().unwrap_or(U256::zero());
+    if index_plus_one == U256::zero() {
+        return;
+    }
+
+    let list = user_token_ids.entry(user).or_insert_with(Vec::new);
+    let idx = (index_plus_one.as_u64() as usize).saturating_sub(1);
+    let last_idx = list.len().saturating_sub(1);
+
+    if idx < list.len() {
+        if idx != last_idx {
+            let last = list[last_idx];
+            list[idx] = last;
+            index_map.insert((user, last), U256::from(idx as u64 + 1));
+        }
+        list.pop();
+    }
+
+    index_map.remove(&key);
+
+    set_user_token_ids(user_token_ids);
+    set_user_token_index_plus_one(index_map);
+}
+
+fn bump_window(entry: &mut (u64, u64), window_start: u64, limit: u64) {
+    if entry.0 != window_start {
+        *entry = (window_start, 0);
+    }
+    if limit != 0 && entry.1 >= limit {
+        runtime::revert(ApiError::User(40));
+    }
+    entry.1 += 1;
+}
+
+fn enforce_mint_limits(minter: Key, user: Key, now: u64) {
+    enforce_minter_limit(minter, now);
+    enforce_minter_user_limit(minter, user, now);
+}
+
+fn enforce_minter_limit(minter: Key, now: u64) {
+    let (window, _per_user_limit, per_minter_limit) = get_mint_limits();
+    if window == 0 || per_minter_limit == 0 {
+        return;
+    }
+    let window_start = now - now % window;
+
+    let mut minter_windows = get_minter_windows();
+    bump_window(
+        minter_windows.entry(minter).or_insert((window_start, 0)),
+        window_start,
+        per_minter_limit,
+    );
+    set_minter_windows(minter_windows);
+}
+
+fn enforce_minter_user_limit(minter: Key, user: Key, now: u64) {
+    let (window, per_user_limit, _per_minter_limit) = get_mint_limits();
+    if window == 0 || per_user_limit == 0 {
+        return;
+    }
+    let window_start = now - now % window;
+
+    let mut minter_user_windows = get_minter_user_windows();
+    bump_window(
+        minter_user_windows
+            .entry((minter, user))
+            .or_insert((window_start, 0)),
+        window_start,
+        per_user_limit,
+    );
+    set_minter_user_windows(minter_user_windows);
+}
+
+fn record_completion_count(user: Key, media_id: String, now: u64) {
+    let mut counts = get_completion_counts();
+    let entry = counts.entry((user, media_id)).or_insert((0u64, 0u64));
+    entry.0 += 1;
+    entry.1 = now;
+    set_completion_counts(counts);
+}
+
+fn burn_internal(user: Key, token_id: U256) {
+    let mut token_owner = get_token_owner();
+    token_owner.remove(&token_id);
+    set_token_owner(token_owner);
+
+    let mut token_media = get_token_media_id();
+    let media_id = token_media.remove(&token_id).unwrap_or_default();
+    set_token_media_id(token_media);
+
+    let mut completion = get_completion_token_id();
+    completion.remove(&(user, media_id.clone()));
+    set_completion_token_id(completion);
+
+    let mut counts = get_completion_counts();
+    counts.remove(&(user, media_id.clone()));
+    set_completion_counts(counts);
+    record_burn_stats(user, &media_id);
+
+    let mut token_info = get_token_info_map();
+    token_info.remove(&token_id);
+    set_token_info_map(token_info);
+
+    let mut token_campaign_uris = get_token_campaign_uris();
+    if token_campaign_uris.remove(&token_id).is_some() {
+        set_token_campaign_uris(token_campaign_uris);
+    }
+
+    clear_token_rating_internal(token_id, media_id.clone());
+    remove_user_token_id(user, token_id);
+    remove_media_completer(user, media_id.clone());
+    leave_media_memberships_internal(user, media_id.clone());
+    notify_hooks(HOOK_ON_BURN, user, media_id, token_id);
+}
+
+fn leave_media_memberships_internal(user: Key, media_id: String) {
+    remove_group_member_internal(user, media_id.clone());
+    leave_media_rooms_internal(user, media_id.clone());
+    prune_rule_groups_internal(user, &media_id);
+}
+
+fn reward_token_hash() -> ContractHash {
+    let token = get_reward_token().unwrap_or_revert_with(ApiError::User(46));
+    ContractHash::new(token.into_hash_addr().unwrap_or_revert())
+}
+
+fn pay_completion_reward(user: Key, media_id: String, kind: u8) {
+    if get_reward_token().is_none() {
+        return;
+    }
+    let mut rewarded = get_rewarded_completions();
+    if rewarded.contains_key(&(user, media_id.clone())) {
+        return;
+    }
+    let amount = get_reward_table().get(&kind).copied().unwrap_or_default();
+    if amount.is_zero() {
+        return;
+    }
+    let token = reward_token_hash();
+    let pool: U256 = runtime::call_contract(
+        token,
+        "balance_of",
+        runtime_args! { ARG_ADDRESS => self_key() },
+    );
+    if pool < amount {
+        return;
+    }
+    runtime::call_contract::<()>(
+        token,
+        "transfer",
+        runtime_args! { ARG_RECIPIENT => user, ARG_AMOUNT => amount },
+    );
+    rewarded.insert((user, media_id), amount);
+    set_rewarded_completions(rewarded);
+    set_total_rewards_paid(get_total_rewards_paid() + amount);
+}
+
+fn notify_hooks(entry_point: &str, user: Key, media_id: String, token_id: U256) {
+    if get_hooks_disabled() {
+        return;
+    }
+    for (hook, can_veto) in get_hooks().iter() {
+        let contract_hash = ContractHash::new(hook.into_hash_addr().unwrap_or_revert());
+        let accepted: bool = runtime::call_contract(
+            contract_hash,
+            entry_point,
+            runtime_args! {
+                ARG_USER => user,
+                ARG_MEDIA_ID => media_id.clone(),
+                ARG_TOKEN_ID => token_id,
+            },
+        );
+        if !accepted && *can_veto && entry_point == HOOK_ON_COMPLETION {
+            runtime::revert(ApiError::User(43));
+        }
+    }
+}
+
+fn award_achievements_internal(user: Key, first_completer: bool) {
+    let achievements = get_achievements();
+    if achievements.is_empty() {
+        return;
+    }
+
+    let tokens = get_user_token_ids().get(&user).cloned().unwrap_or_default();
+    let token_media = get_token_media_id();
+    let media = get_media();
+    let mut kind_counts = BTreeMap::<u8, u64>::new();
+    let mut total = 0u64;
+    for token_id in tokens.iter() {
+        if let Some(media_id) = token_media.get(token_id) {
+            total += 1;
+            if let Some(kind) = media.get(media_id).map(|v| v.0) {
+                *kind_counts.entry(kind).or_insert(0) += 1;
+            }
+        }
+    }
+
+    let mut awarded = get_achievement_awarded();
+    let mut user_achievements = get_user_achievements();
+    let mut token_owner = get_token_owner();
+    let mut achievement_tokens = get_achievement_tokens();
+    let mut changed = false;
+    for (achievement_id, ((rule, kind, threshold), uri)) in achievements.iter() {
+        let key = (user, achievement_id.clone());
+        if awarded.contains_key(&key) {
+            continue;
+        }
+        let earned = match *rule {
+            ACHIEVEMENT_TOTAL_COMPLETIONS => total >= *threshold,
+            ACHIEVEMENT_KIND_COUNT => kind_counts.get(kind).copied().unwrap_or(0) >= *threshold,
+            ACHIEVEMENT_ALL_KINDS => MEDIA_KINDS.iter().all(|k| kind_counts.contains_key(k)),
+            ACHIEVEMENT_FIRST_COMPLETER => first_completer,
+            _ => false,
+        };
+        if earned {
+            let token_id = next_achievement_token_id();
+            awarded.insert(key, token_id);
+            user_achievements
+                .entry(user)
+                .or_default()
+                .push((achievement_id.clone(), token_id));
+            token_owner.insert(token_id, user);
+            achievement_tokens.insert(token_id, (achievement_id.clone(), uri.clone()));
+            changed = true;
+        }
+    }
+    if changed {
+        set_achievement_awarded(awarded);
+        set_user_achievements(user_achievements);
+        set_token_owner(token_owner);
+        set_achievement_tokens(achievement_tokens);
+    }
+}
+
+fn burn_achievement_internal(user: Key, token_id: U256) {
+    let mut token_owner = get_token_owner();
+    token_owner.remove(&token_id);
+    set_token_owner(token_owner);
+
+    let mut achievement_tokens = get_achievement_tokens();
+    achievement_tokens.remove(&token_id);
+    set_achievement_tokens(achievement_tokens);
+
+    let mut user_achievements = get_user_achievements();
+    if let Some(list) = user_achievements.get_mut(&user) {
+        list.retain(|(_achievement_id, id)| *id != token_id);
+    }
+    set_user_achievements(user_achievements);
+}
+
+fn apply_campaign_edition(token_id: U256, media_id: String, now: u64) {
+    let mut campaigns = get_campaigns();
+    let edition_uri = match campaigns.get_mut(&media_id) {
+        Some(((_creator, cap, minted), (start, end), edition_uri))
+            if *start <= now && now < *end && *minted < *cap =>
+        {
+            *minted += 1;
+            edition_uri.clone()
+        }
+        _ => return,
+    };
+    set_campaigns(campaigns);
+
+    let mut token_campaign_uris = get_token_campaign_uris();
+    token_campaign_uris.insert(token_id, edition_uri);
+    set_token_campaign_uris(token_campaign_uris);
+}
+
+fn record_completion_stats(user: Key, media_id: &String, now: u64, minted: bool) {
+    let kind = get_media().get(media_id).map(|v| v.0).unwrap_or(0);
+    let mut stats = get_user_stats_map();
+    let ((total, first, last), kinds, _groups) = stats.entry(user).or_default();
+    if minted {
+        *total += 1;
+        *kinds.entry(kind).or_insert(0) += 1;
+    }
+    if *first == 0 {
+        *first = now;
+    }
+    *last = now;
+    set_user_stats_map(stats);
+}
+
+fn record_burn_stats(user: Key, media_id: &String) {
+    let kind = get_media().get(media_id).map(|v| v.0).unwrap_or(0);
+    let mut stats = get_user_stats_map();
+    if let Some(((total, _first, _last), kinds, _groups)) = stats.get_mut(&user) {
+        *total = total.saturating_sub(1);
+        if let Some(count) = kinds.get_mut(&kind) {
+            *count = count.saturating_sub(1);
+            if *count == 0 {
+                kinds.remove(&kind);
+            }
+        }
+        set_user_stats_map(stats);
+    }
+}
+
+fn record_group_stats(user: Key, joined: bool) {
+    let mut stats = get_user_stats_map();
+    let (_completions, _kinds, (current, joined_total)) = stats.entry(user).or_default();
+    if joined {
+        *current += 1;
+        *joined_total += 1;
+    } else {
+        *current = current.saturating_sub(1);
+    }
+    set_user_stats_map(stats);
+}
+
+fn register_and_complete(to: Key, kind: u8, uri: String, name: String) -> U256 {
+    let media_id = media_id_hex(kind, &uri, &name);
+
+    let mut media = get_media();
+    let exists = media.get(&media_id).map(|v| v.1).unwrap_or(false);
+    if !exists {
+        media.insert(media_id.clone(), (kind, true, (uri, name)));
+        set_media(media);
+    }
+
+    let token_id = complete_internal(to, media_id.clone());
+    pay_completion_reward(to, media_id, kind);
+    token_id
+}
+
+fn complete_internal(user: Key, media_id: String) -> U256 {
+    let mut completion = get_completion_token_id();
+    let now = u64::from(runtime::get_blocktime());
+    enforce_mint_limits(caller_key(), user, now);
+    let existing = completion
+        .get(&(user, media_id.clone()))
+        .copied()
+        .unwrap_or(U256::zero());
+    if existing != U256::zero() {
+        if !get_repeat_completions_allowed() {
+            runtime::revert(ApiError::User(4));
+        }
+        record_completion_count(user, media_id.clone(), now);
+        record_completion_stats(user, &media_id, now, false);
+        return existing;
+    }
+
+    let cooldown_until = get_revocations()
+        .get(&(user, media_id.clone()))
+        .map(|(_reason, _revoked_at, until)| *until)
+        .unwrap_or(0);
+    if now < cooldown_until {
+        runtime::revert(ApiError::User(36));
+    }
+
+    let token_id = get_next_token_id();
+    set_next_token_id(token_id + U256::one());
+
+    let mut token_owner = get_token_owner();
+    let mut token_media = get_token_media_id();
+    token_owner.insert(token_id, user);
+    token_media.insert(token_id, media_id.clone());
+    set_token_owner(token_owner);
+    set_token_media_id(token_media);
+
+    completion.insert((user, media_id.clone()), token_id);
+    set_completion_token_id(completion);
+    record_completion_count(user, media_id.clone(), now);
+    record_completion_stats(user, &media_id, now, true);
+
+    let mut editions = get_media_edition_counter();
+    let edition = editions.get(&media_id).copied().unwrap_or(0) + 1;
+    editions.insert(media_id.clone(), edition);
+    set_media_edition_counter(editions);
+
+    let mut token_info = get_token_info_map();
+    token_info.insert(token_id, (edition, now, edition <= get_first_edition_limit()));
+    set_token_info_map(token_info);
+    apply_campaign_edition(token_id, media_id.clone(), now);
+
+    let mut user_tokens = get_user_token_ids();
+    let mut user_index = get_user_token_index_plus_one();
+    let list = user_tokens.entry(user).or_insert_with(Vec::new);
+    list.push(token_id);
+    user_index.insert((user, token_id), U256::from(list.len() as u64));
+    set_user_token_ids(user_tokens);
+    set_user_token_index_plus_one(user_index);
+
+    add_media_completer(user, media_id.clone());
+    let first_completer = record_first_completer(user, &media_id);
+    award_achievements_internal(user, first_completer);
+    record_challenge_progress_internal(user, media_id.clone(), now);
+    notify_hooks(HOOK_ON_COMPLETION, user, media_id, token_id);
+
+    token_id
+}
+
+#[no_mangle]
+pub extern "C" fn compute_media_id() {
+    let kind: u8 = runtime::get_named_arg(ARG_KIND);
+    let uri: String = runtime::get_named_arg(ARG_URI);
+    let name: String = runtime::get_named_arg(ARG_NAME);
+    let media_id = media_id_hex(kind, &uri, &name);
+    runtime::ret(CLValue::from_t(media_id).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn set_registrar() {
+    require_owner();
+    let registrar: Key = runtime::get_named_arg(ARG_REGISTRAR);
+    let allowed: bool = runtime::get_named_arg(ARG_ALLOWED);
+    let mut registrars: BTreeMap<Key, bool> =
+        storage::read(get_or_init_uref::<BTreeMap<Key, bool>>(REGISTRAR_KEY))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default();
+    registrars.insert(registrar, allowed);
+    storage::write(
+        get_or_init_uref::<BTreeMap<Key, bool>>(REGISTRAR_KEY),
+        registrars,
+    );
+}
+
+#[no_mangle]
+pub extern "C" fn set_backend() {
+    require_owner();
+    let backend: Key = runtime::get_named_arg(ARG_BACKEND);
+    storage::write(get_uref(BACKEND_KEY), backend);
+}
+
+#[no_mangle]
+pub extern "C" fn complete_and_register_by_external_id() {
+    require_not_paused(PAUSE_MINTING);
+    require_backend();
+    let to: Key = runtime::get_named_arg(ARG_TO);
+    let kind: u8 = runtime::get_named_arg(ARG_KIND);
+    let uri: String = runtime::get_named_arg(ARG_URI);
+    let name: String = runtime::get_named_arg(ARG_NAME);
+
+    let token_id = register_and_complete(to, kind, uri, name);
+    runtime::ret(CLValue::from_t(token_id).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn set_media_uri() {
+    require_not_paused(PAUSE_METADATA);
+    require_owner();
+    let media_id: String = runtime::get_named_arg(ARG_MEDIA_ID);
+    let uri: String = runtime::get_named_arg(ARG_URI);
+    let mut media = get_media();
+    let item = match media.get_mut(&media_id) {
+        Some(v) if v.1 => v,
+        _ => runtime::revert(ApiError::User(5)),
+    };
+    item.2.0 = uri;
+    set_media(media);
+}
+
+#[no_mangle]
+pub extern "C" fn set_media_kind() {
+    require_not_paused(PAUSE_METADATA);
+    require_owner();
+    let media_id: String = runtime::get_named_arg(ARG_MEDIA_ID);
+    let kind: u8 = runtime::get_named_arg(ARG_KIND);
+    let mut media = get_media();
+    let item = match media.get_mut(&media_id) {
+        Some(v) if v.1 => v,
+        _ => runtime::revert(ApiError::User(5)),
+    };
+    item.0 = kind;
+    set_media(media);
+}
+
+#[no_mangle]
+pub extern "C" fn media_info() {
+    let media_id: String = runtime::get_named_arg(ARG_MEDIA_ID);
+    let media = get_media();
+    let (kind, exists, (uri, name)) = media
+        .get(&media_id)
+        .cloned()
+        .unwrap_or((0u8, false, (String::new(), String::new())));
+    runtime::ret(CLValue::from_t((exists, kind, (uri, name))).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn has_completed() {
+    let user: Key = runtime::get_named_arg(ARG_USER);
+    let media_id: String = runtime::get_named_arg(ARG_MEDIA_ID);
+    let has = is_completed(user, media_id);
+    runtime::ret(CLValue::from_t(has).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn user_token_ids() {
+    let user: Key = runtime::get_named_arg(ARG_USER);
+    let user_tokens = get_user_token_ids();
+    let tokens = user_tokens.get(&user).cloned().unwrap_or_default();
+    runtime::ret(CLValue::from_t(tokens).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn can_text() {
+    let from: Key = runtime::get_named_arg(ARG_FROM);
+    let to: Key = runtime::get_named_arg(ARG_TO);
+    let media_id: String = runtime::get_named_arg(ARG_MEDIA_ID);
+    let from_ok = is_completed(from, media_id.clone());
+    let to_ok = is_completed(to, media_id);
+    let allowed = from_ok && to_ok && dm_allowed(from, to, true);
+    runtime::ret(CLValue::from_t(allowed).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn can_join_group() {
+    let user: Key = runtime::get_named_arg(ARG_USER);
+    let media_id: String = runtime::get_named_arg(ARG_MEDIA_ID);
+    let has = is_eligible_for_group(user, &media_id);
+    let group_index = get_group_index_plus_one();
+    let in_group = group_index
+        .get(&(media_id, user))
+        .copied()
+        .unwrap_or(U256::zero())
+        != U256::zero();
+    runtime::ret(CLValue::from_t(has && !in_group).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn join_group() {
+    require_not_paused(PAUSE_GROUPS);
+    let caller = caller_key();
+    let media_id: String = runtime::get_named_arg(ARG_MEDIA_ID);
+    if !is_eligible_for_group(caller, &media_id) {
+        runtime::revert(ApiError::User(6));
+    }
+    if group_join_policy(&media_id) != JOIN_POLICY_OPEN {
+        runtime::revert(ApiError::User(13));
+    }
+    join_group_internal(caller, media_id);
+}
+
+#[no_mangle]
+pub extern "C" fn leave_group() {
+    require_not_paused(PAUSE_GROUPS);
+    let caller = caller_key();
+    let media_id: String = runtime::get_named_arg(ARG_MEDIA_ID);
+    let group_index = get_group_index_plus_one();
+    let in_group = group_index
+        .get(&(media_id.clone(), caller))
+        .copied()
+        .unwrap_or(U256::zero())
+        != U256::zero();
+    if !in_group {
+        runtime::revert(ApiError::User(7));
+    }
+    remove_group_member_internal(caller, media_id);
+}
+
+#[no_mangle]
+pub extern "C" fn is_group_member() {
+    let media_id: String = runtime::get_named_arg(ARG_MEDIA_ID);
+    let user: Key = runtime::get_named_arg(ARG_USER);
+    let group_index = get_group_index_plus_one();
+    let in_group = group_index
+        .get(&(media_id, user))
+        .copied()
+        .unwrap_or(U256::zero())
+        != U256::zero();
+    runtime::ret(CLValue::from_t(in_group).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn group_member_count() {
+    let media_id: String = runtime::get_named_arg(ARG_MEDIA_ID);
+    let count = get_group_members_map()
+        .get(&media_id)
+        .map(|v| U256::from(v.len() as u64))
+        .unwrap_or(U256::zero());
+    runtime::ret(CLValue::from_t(count).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn group_members_page() {
+    let media_id: String = runtime::get_named_arg(ARG_MEDIA_ID);
+    let start: U256 = runtime::get_named_arg(ARG_START);
+    let count: U256 = runtime::get_named_arg(ARG_COUNT);
+    let visible = visible_group_members(&media_id, caller_key());
+    runtime::ret(CLValue::from_t(page_of(&visible, start, count)).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn group_member_at() {
+    let media_id: String = runtime::get_named_arg(ARG_MEDIA_ID);
+    let index: U256 = runtime::get_named_arg(ARG_INDEX);
+    let idx = index.as_u64() as usize;
+    let list = visible_group_members(&media_id, caller_key());
+    if idx >= list.len() {
+        runtime::revert(ApiError::User(8));
+    }
+    runtime::ret(CLValue::from_t(list[idx]).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn owner_of() {
+    let token_id: U256 = runtime::get_named_arg(ARG_TOKEN_ID);
+    let token_owner = get_token_owner();
+    let owner = token_owner.get(&token_id).copied().unwrap_or_revert();
+    runtime::ret(CLValue::from_t(owner).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn token_uri() {
+    let token_id: U256 = runtime::get_named_arg(ARG_TOKEN_ID);
+    let token_owner = get_token_owner();
+    if token_owner.get(&token_id).is_none() {
+        runtime::revert(ApiError::User(9));
+    }
+    if let Some((_achievement_id, achievement_uri)) = get_achievement_tokens().get(&token_id) {
+        runtime::ret(CLValue::from_t(achievement_uri.clone()).unwrap_or_revert());
+    }
+    if let Some(edition_uri) = get_token_campaign_uris()
+        .get(&token_id)
+        .filter(|v| !v.is_empty())
+    {
+        runtime::ret(CLValue::from_t(edition_uri.clone()).unwrap_or_revert());
+    }
+    let token_media = get_token_media_id();
+    let media_id = token_media.get(&token_id).cloned().unwrap_or_default();
+    let media = get_media();
+    let (kind, uri) = media
+        .get(&media_id)
+        .map(|v| (v.0, v.2.0.clone()))
+        .unwrap_or((0u8, String::new()));
+    if !uri.is_empty() {
+        runtime::ret(CLValue::from_t(uri).unwrap_or_revert());
+    }
+    let base = get_kind_base_uris()
+        .get(&kind)
+        .cloned()
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(get_base_uri);
+    if base.is_empty() {
+        let name = media.get(&media_id).map(|v| v.2.1.clone()).unwrap_or_default();
+        let (edition, completed_at, _first_edition) = get_token_info_map()
+            .get(&token_id)
+            .copied()
+            .unwrap_or((0u64, 0u64, false));
+        let svg = render_svg_badge(&name, kind, completed_at, edition);
+        let data_uri = format!("data:image/svg+xml;base64,{}", base64_encode(svg.as_bytes()));
+        runtime::ret(CLValue::from_t(data_uri).unwrap_or_revert());
+    }
+    let resolved = resolve_uri_template(&base, &media_id, token_id, kind);
+    runtime::ret(CLValue::from_t(resolved).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn set_base_uri() {
+    require_not_paused(PAUSE_METADATA);
+    require_owner();
+    let base: String = runtime::get_named_arg(ARG_BASE_URI);
+    set_base_uri_internal(base);
+}
+
+#[no_mangle]
+pub extern "C" fn burn() {
+    require_not_paused(PAUSE_BURNING);
+    let token_id: U256 = runtime::get_named_arg(ARG_TOKEN_ID);
+    let caller = caller_key();
+    require_token_owner(token_id, caller);
+    if get_achievement_tokens().contains_key(&token_id) {
+        burn_achievement_internal(caller, token_id);
+        return;
+    }
+    burn_internal(caller, token_id);
+}
+
+#[no_mangle]
+pub extern "C" fn get_similars_from_tokens() {
+    let token_ids: Vec<U256> = runtime::get_named_arg(ARG_TOKEN_IDS);
+    if token_ids.is_empty() {
+        runtime::ret(CLValue::from_t(Vec::<Key>::new()).unwrap_or_revert());
+    }
+
+    let token_owner = get_token_owner();
+    let user = match token_owner.get(&token_ids[0]) {
+        Some(v) => *v,
+        None => runtime::revert(ApiError::User(9)),
+    };
+
+    let token_media = get_token_media_id();
+    let media_completers = get_media_completers();
+    let viewer = caller_key();
+
+    let mut out_set = BTreeSet::<Key>::new();
+    for token_id in token_ids.iter() {
+        require_token_owner(*token_id, user);
+        let media_id = token_media.get(token_id).cloned().unwrap_or_default();
+        if let Some(completers) = media_completers.get(&media_id) {
+            let viewer_completed = is_completed(viewer, media_id.clone());
+            for candidate in visible_completers(completers, viewer, viewer_completed) {
+                if candidate != user {
+                    out_set.insert(candidate);
+                }
+            }
+        }
+    }
+
+    let out: Vec<Key> = out_set.into_iter().collect();
+    runtime::ret(CLValue::from_t(out).unwrap_or_revert());
 }
 
-fn get_media_completers() -> BTreeMap<String, Vec<Key>> {
-    storage::read(get_or_init_uref::<BTreeMap<String, Vec<Key>>>(MEDIA_COMPLETERS_KEY))
-        .unwrap_or_revert_with(ApiError::Read)
-        .unwrap_or_default()
+#[no_mangle]
+pub extern "C" fn get_similars_for_token() {
+    let token_id: U256 = runtime::get_named_arg(ARG_TOKEN_ID);
+    let token_owner = get_token_owner();
+    let user = match token_owner.get(&token_id) {
+        Some(v) => *v,
+        None => runtime::revert(ApiError::User(9)),
+    };
+    let token_media = get_token_media_id();
+    let media_id = token_media.get(&token_id).cloned().unwrap_or_default();
+    let media_completers = get_media_completers();
+    let viewer = caller_key();
+    let mut out = Vec::new();
+    if let Some(completers) = media_completers.get(&media_id) {
+        let viewer_completed = is_completed(viewer, media_id.clone());
+        for candidate in visible_completers(completers, viewer, viewer_completed) {
+            if candidate != user {
+                out.push(candidate);
+            }
+        }
+    }
+    runtime::ret(CLValue::from_t(out).unwrap_or_revert());
 }
 
-fn set_media_completers(v: BTreeMap<String, Vec<Key>>) {
-    storage::write(get_or_init_uref::<BTreeMap<String, Vec<Key>>>(MEDIA_COMPLETERS_KEY), v);
+#[no_mangle]
+pub extern "C" fn create_room() {
+    require_not_paused(PAUSE_GROUPS);
+    let caller = caller_key();
+    let media_id: String = runtime::get_named_arg(ARG_MEDIA_ID);
+    let name: String = runtime::get_named_arg(ARG_NAME);
+    let join_policy: u8 = runtime::get_named_arg(ARG_JOIN_POLICY);
+    require_valid_join_policy(join_policy);
+    if !is_completed(caller, media_id.clone()) {
+        runtime::revert(ApiError::User(6));
+    }
+
+    let room_id = room_id_hex(&media_id, &name);
+    let mut rooms = get_rooms();
+    if rooms.contains_key(&room_id)
+        || get_media().contains_key(&room_id)
+        || get_group_rules().contains_key(&room_id)
+    {
+        runtime::revert(ApiError::User(12));
+    }
+    rooms.insert(room_id.clone(), (media_id.clone(), caller, name));
+    set_rooms(rooms);
+
+    let mut media_rooms = get_media_rooms();
+    media_rooms.entry(media_id).or_default().push(room_id.clone());
+    set_media_rooms(media_rooms);
+
+    let mut policies = get_group_policies();
+    policies.insert(room_id.clone(), join_policy);
+    set_group_policies(policies);
+
+    join_group_internal(caller, room_id.clone());
+    runtime::ret(CLValue::from_t(room_id).unwrap_or_revert());
 }
 
-fn get_media_completer_index_plus_one() -> BTreeMap<(String, Key), U256> {
-    storage::read(
-        get_or_init_uref::<BTreeMap<(String, Key), U256>>(MEDIA_COMPLETER_INDEX_PLUS_ONE_KEY),
-    )
-        .unwrap_or_revert_with(ApiError::Read)
-        .unwrap_or_default()
+#[no_mangle]
+pub extern "C" fn join_room() {
+    require_not_paused(PAUSE_GROUPS);
+    let caller = caller_key();
+    let room_id: String = runtime::get_named_arg(ARG_ROOM_ID);
+    let (media_id, _creator, _name) = get_rooms()
+        .get(&room_id)
+        .cloned()
+        .unwrap_or_revert_with(ApiError::User(11));
+    if !is_completed(caller, media_id) {
+        runtime::revert(ApiError::User(6));
+    }
+    if group_join_policy(&room_id) != JOIN_POLICY_OPEN {
+        runtime::revert(ApiError::User(13));
+    }
+    join_group_internal(caller, room_id);
 }
 
-fn set_media_completer_index_plus_one(v: BTreeMap<(String, Key), U256>) {
-    storage::write(
-        get_or_init_uref::<BTreeMap<(String, Key), U256>>(MEDIA_COMPLETER_INDEX_PLUS_ONE_KEY),
-        v,
-    );
+#[no_mangle]
+pub extern "C" fn leave_room() {
+    require_not_paused(PAUSE_GROUPS);
+    let caller = caller_key();
+    let room_id: String = runtime::get_named_arg(ARG_ROOM_ID);
+    if !get_rooms().contains_key(&room_id) {
+        runtime::revert(ApiError::User(11));
+    }
+    let group_index = get_group_index_plus_one();
+    let in_room = group_index
+        .get(&(room_id.clone(), caller))
+        .copied()
+        .unwrap_or(U256::zero())
+        != U256::zero();
+    if !in_room {
+        runtime::revert(ApiError::User(7));
+    }
+    remove_group_member_internal(caller, room_id);
 }
 
-fn get_group_members_map() -> BTreeMap<String, Vec<Key>> {
-    storage::read(get_or_init_uref::<BTreeMap<String, Vec<Key>>>(GROUP_MEMBERS_KEY))
-        .unwrap_or_revert_with(ApiError::Read)
-        .unwrap_or_default()
+#[no_mangle]
+pub extern "C" fn room_info() {
+    let room_id: String = runtime::get_named_arg(ARG_ROOM_ID);
+    let (media_id, creator, name) = get_rooms()
+        .get(&room_id)
+        .cloned()
+        .unwrap_or_revert_with(ApiError::User(11));
+    let join_policy = group_join_policy(&room_id);
+    runtime::ret(CLValue::from_t((media_id, creator, (name, join_policy))).unwrap_or_revert());
 }
 
-fn set_group_members_map(v: BTreeMap<String, Vec<Key>>) {
-    storage::write(get_or_init_uref::<BTreeMap<String, Vec<Key>>>(GROUP_MEMBERS_KEY), v);
+#[no_mangle]
+pub extern "C" fn media_rooms() {
+    let media_id: String = runtime::get_named_arg(ARG_MEDIA_ID);
+    let rooms = get_media_rooms().get(&media_id).cloned().unwrap_or_default();
+    runtime::ret(CLValue::from_t(rooms).unwrap_or_revert());
 }
 
-fn get_group_index_plus_one() -> BTreeMap<(String, Key), U256> {
-    storage::read(get_or_init_uref::<BTreeMap<(String, Key), U256>>(GROUP_INDEX_PLUS_ONE_KEY))
-        .unwrap_or_revert_with(ApiError::Read)
-        .unwrap_or_default()
+#[no_mangle]
+pub extern "C" fn room_members_page() {
+    let room_id: String = runtime::get_named_arg(ARG_ROOM_ID);
+    let start: U256 = runtime::get_named_arg(ARG_START);
+    let count: U256 = runtime::get_named_arg(ARG_COUNT);
+    if !get_rooms().contains_key(&room_id) {
+        runtime::revert(ApiError::User(11));
+    }
+    let visible = visible_group_members(&room_id, caller_key());
+    runtime::ret(CLValue::from_t(page_of(&visible, start, count)).unwrap_or_revert());
 }
 
-fn set_group_index_plus_one(v: BTreeMap<(String, Key), U256>) {
-    storage::write(get_or_init_uref::<BTreeMap<(String, Key), U256>>(GROUP_INDEX_PLUS_ONE_KEY), v);
+#[no_mangle]
+pub extern "C" fn create_group() {
+    require_not_paused(PAUSE_GROUPS);
+    let caller = caller_key();
+    let name: String = runtime::get_named_arg(ARG_NAME);
+    let rule: u8 = runtime::get_named_arg(ARG_RULE);
+    let kind: u8 = runtime::get_named_arg(ARG_KIND);
+    let required: u64 = runtime::get_named_arg(ARG_REQUIRED);
+    let media_ids: Vec<String> = runtime::get_named_arg(ARG_MEDIA_IDS);
+    let join_policy: u8 = runtime::get_named_arg(ARG_JOIN_POLICY);
+    require_valid_rule((rule, kind, required), &media_ids);
+    require_valid_join_policy(join_policy);
+
+    let group_id = group_id_hex(caller, &name);
+    let mut rules = get_group_rules();
+    if rules.contains_key(&group_id)
+        || get_media().contains_key(&group_id)
+        || get_rooms().contains_key(&group_id)
+    {
+        runtime::revert(ApiError::User(15));
+    }
+    index_rule_group(&group_id, (rule, kind, required), &media_ids);
+    rules.insert(group_id.clone(), ((caller, name), (rule, kind, required), media_ids));
+    set_group_rules(rules);
+
+    let mut policies = get_group_policies();
+    policies.insert(group_id.clone(), join_policy);
+    set_group_policies(policies);
+
+    runtime::ret(CLValue::from_t(group_id).unwrap_or_revert());
 }
 
-fn get_base_uri() -> String {
-    storage::read(get_uref(BASE_URI_KEY))
-        .unwrap_or_revert_with(ApiError::Read)
-        .unwrap_or_default()
+#[no_mangle]
+pub extern "C" fn group_info() {
+    let group_id: String = runtime::get_named_arg(ARG_GROUP_ID);
+    let info = get_group_rules()
+        .get(&group_id)
+        .cloned()
+        .unwrap_or_revert_with(ApiError::User(16));
+    runtime::ret(CLValue::from_t(info).unwrap_or_revert());
 }
 
-fn set_base_uri_internal(v: String) {
-    storage::write(get_uref(BASE_URI_KEY), v);
+#[no_mangle]
+pub extern "C" fn set_group_policy() {
+    require_not_paused(PAUSE_GROUPS);
+    let caller = caller_key();
+    let group_id: String = runtime::get_named_arg(ARG_GROUP_ID);
+    let join_policy: u8 = runtime::get_named_arg(ARG_JOIN_POLICY);
+    require_valid_join_policy(join_policy);
+    require_group_admin(&group_id, caller);
+    let mut policies = get_group_policies();
+    policies.insert(group_id, join_policy);
+    set_group_policies(policies);
 }
 
-fn media_id_hex(kind: u8, uri: &str, name: &str) -> String {
-    let mut bytes = Vec::new();
-    bytes.push(kind);
-    bytes.extend_from_slice(uri.as_bytes());
-    bytes.push(0);
-    bytes.extend_from_slice(name.as_bytes());
-    let digest = runtime::blake2b(bytes);
-    let mut out = String::with_capacity(64);
-    for b in digest.iter() {
-        out.push_str(&hex_byte(*b));
+#[no_mangle]
+pub extern "C" fn invite_to_group() {
+    require_not_paused(PAUSE_GROUPS);
+    let caller = caller_key();
+    let group_id: String = runtime::get_named_arg(ARG_GROUP_ID);
+    let user: Key = runtime::get_named_arg(ARG_USER);
+    require_group_admin(&group_id, caller);
+    if is_group_member_internal(user, &group_id) {
+        runtime::revert(ApiError::User(20));
     }
-    out
+    let mut invites = get_group_invites();
+    invites.insert((group_id, user), caller);
+    set_group_invites(invites);
+}
+
+#[no_mangle]
+pub extern "C" fn accept_invite() {
+    require_not_paused(PAUSE_GROUPS);
+    let caller = caller_key();
+    let group_id: String = runtime::get_named_arg(ARG_GROUP_ID);
+    let mut invites = get_group_invites();
+    if invites.remove(&(group_id.clone(), caller)).is_none() {
+        runtime::revert(ApiError::User(18));
+    }
+    if !is_eligible_for_group(caller, &group_id) {
+        runtime::revert(ApiError::User(6));
+    }
+    set_group_invites(invites);
+    remove_join_request_internal(caller, group_id.clone());
+    join_group_internal(caller, group_id);
+}
+
+#[no_mangle]
+pub extern "C" fn has_invite() {
+    let group_id: String = runtime::get_named_arg(ARG_GROUP_ID);
+    let user: Key = runtime::get_named_arg(ARG_USER);
+    let invited = get_group_invites().contains_key(&(group_id, user));
+    runtime::ret(CLValue::from_t(invited).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn request_to_join() {
+    require_not_paused(PAUSE_GROUPS);
+    let caller = caller_key();
+    let group_id: String = runtime::get_named_arg(ARG_GROUP_ID);
+    if group_join_policy(&group_id) != JOIN_POLICY_APPROVAL {
+        runtime::revert(ApiError::User(19));
+    }
+    if is_group_member_internal(caller, &group_id) {
+        runtime::revert(ApiError::User(20));
+    }
+    if !is_eligible_for_group(caller, &group_id) {
+        runtime::revert(ApiError::User(6));
+    }
+    add_join_request_internal(caller, group_id);
+}
+
+#[no_mangle]
+pub extern "C" fn approve_join_request() {
+    require_not_paused(PAUSE_GROUPS);
+    let caller = caller_key();
+    let group_id: String = runtime::get_named_arg(ARG_GROUP_ID);
+    let user: Key = runtime::get_named_arg(ARG_USER);
+    require_group_admin(&group_id, caller);
+    if !has_join_request(user, &group_id) {
+        runtime::revert(ApiError::User(21));
+    }
+    if !is_eligible_for_group(user, &group_id) {
+        runtime::revert(ApiError::User(6));
+    }
+    remove_join_request_internal(user, group_id.clone());
+    join_group_internal(user, group_id);
+}
+
+#[no_mangle]
+pub extern "C" fn reject_join_request() {
+    require_not_paused(PAUSE_GROUPS);
+    let caller = caller_key();
+    let group_id: String = runtime::get_named_arg(ARG_GROUP_ID);
+    let user: Key = runtime::get_named_arg(ARG_USER);
+    require_group_admin(&group_id, caller);
+    if !has_join_request(user, &group_id) {
+        runtime::revert(ApiError::User(21));
+    }
+    remove_join_request_internal(user, group_id);
+}
+
+#[no_mangle]
+pub extern "C" fn join_request_count() {
+    let group_id: String = runtime::get_named_arg(ARG_GROUP_ID);
+    let count = get_join_requests()
+        .get(&group_id)
+        .map(|v| U256::from(v.len() as u64))
+        .unwrap_or(U256::zero());
+    runtime::ret(CLValue::from_t(count).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn join_requests_page() {
+    let group_id: String = runtime::get_named_arg(ARG_GROUP_ID);
+    let start: U256 = runtime::get_named_arg(ARG_START);
+    let count: U256 = runtime::get_named_arg(ARG_COUNT);
+    let requests = get_join_requests();
+    let list = requests.get(&group_id).cloned().unwrap_or_default();
+    let viewer = caller_key();
+    let visible = if is_group_admin(&group_id, viewer) {
+        list
+    } else {
+        visible_completers(&list, viewer, is_group_member_internal(viewer, &group_id))
+    };
+    runtime::ret(CLValue::from_t(page_of(&visible, start, count)).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn set_messaging_preference() {
+    require_not_paused(PAUSE_GROUPS);
+    let caller = caller_key();
+    let preference: u8 = runtime::get_named_arg(ARG_PREFERENCE);
+    if preference != MESSAGING_CO_COMPLETERS
+        && preference != MESSAGING_MUTUAL_FOLLOWS
+        && preference != MESSAGING_NOBODY
+    {
+        runtime::revert(ApiError::User(22));
+    }
+    let mut prefs = get_messaging_prefs();
+    prefs.insert(caller, preference);
+    set_messaging_prefs(prefs);
+}
+
+#[no_mangle]
+pub extern "C" fn messaging_preference() {
+    let user: Key = runtime::get_named_arg(ARG_USER);
+    runtime::ret(CLValue::from_t(messaging_preference_of(user)).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn block_user() {
+    require_not_paused(PAUSE_GROUPS);
+    let caller = caller_key();
+    let user: Key = runtime::get_named_arg(ARG_USER);
+    if user == caller {
+        runtime::revert(ApiError::User(23));
+    }
+    let mut blocks = get_blocks();
+    blocks.insert((caller, user), true);
+    set_blocks(blocks);
+    remove_follow_internal(user, caller);
+}
+
+#[no_mangle]
+pub extern "C" fn unblock_user() {
+    require_not_paused(PAUSE_GROUPS);
+    let caller = caller_key();
+    let user: Key = runtime::get_named_arg(ARG_USER);
+    let mut blocks = get_blocks();
+    blocks.remove(&(caller, user));
+    set_blocks(blocks);
+}
+
+#[no_mangle]
+pub extern "C" fn is_blocked() {
+    let user: Key = runtime::get_named_arg(ARG_USER);
+    let target: Key = runtime::get_named_arg(ARG_TARGET);
+    runtime::ret(CLValue::from_t(is_blocked_internal(user, target)).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn can_message() {
+    let from: Key = runtime::get_named_arg(ARG_FROM);
+    let to: Key = runtime::get_named_arg(ARG_TO);
+    let allowed = dm_allowed(from, to, shares_any_completion(from, to));
+    runtime::ret(CLValue::from_t(allowed).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn set_follow_gated() {
+    require_owner();
+    let enabled: bool = runtime::get_named_arg(ARG_ENABLED);
+    storage::write(get_or_init_uref::<bool>(FOLLOW_GATED_KEY), enabled);
+}
+
+#[no_mangle]
+pub extern "C" fn follow() {
+    require_not_paused(PAUSE_GROUPS);
+    let caller = caller_key();
+    let user: Key = runtime::get_named_arg(ARG_USER);
+    if user == caller {
+        runtime::revert(ApiError::User(24));
+    }
+    if is_blocked_internal(user, caller) {
+        runtime::revert(ApiError::User(25));
+    }
+    if get_follow_gated() && !shares_listed_completion(caller, user) {
+        runtime::revert(ApiError::User(26));
+    }
+    add_follow_internal(caller, user);
+}
+
+#[no_mangle]
+pub extern "C" fn unfollow() {
+    require_not_paused(PAUSE_GROUPS);
+    let caller = caller_key();
+    let user: Key = runtime::get_named_arg(ARG_USER);
+    if !is_following(caller, user) {
+        runtime::revert(ApiError::User(27));
+    }
+    remove_follow_internal(caller, user);
+}
+
+#[no_mangle]
+pub extern "C" fn followers_page() {
+    let user: Key = runtime::get_named_arg(ARG_USER);
+    let start: U256 = runtime::get_named_arg(ARG_START);
+    let count: U256 = runtime::get_named_arg(ARG_COUNT);
+    let followers = get_followers();
+    let list = followers.get(&user).cloned().unwrap_or_default();
+    let visible = visible_social_keys(&list, caller_key());
+    runtime::ret(CLValue::from_t(page_of(&visible, start, count)).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn following_page() {
+    let user: Key = runtime::get_named_arg(ARG_USER);
+    let start: U256 = runtime::get_named_arg(ARG_START);
+    let count: U256 = runtime::get_named_arg(ARG_COUNT);
+    let following = get_following();
+    let list = following.get(&user).cloned().unwrap_or_default();
+    let visible = visible_social_keys(&list, caller_key());
+    runtime::ret(CLValue::from_t(page_of(&visible, start, count)).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn is_mutual() {
+    let user: Key = runtime::get_named_arg(ARG_USER);
+    let target: Key = runtime::get_named_arg(ARG_TARGET);
+    let mutual = is_following(user, target) && is_following(target, user);
+    runtime::ret(CLValue::from_t(mutual).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn rate_media() {
+    require_not_paused(PAUSE_METADATA);
+    let caller = caller_key();
+    let media_id: String = runtime::get_named_arg(ARG_MEDIA_ID);
+    let rating: u8 = runtime::get_named_arg(ARG_RATING);
+    if !(RATING_MIN..=RATING_MAX).contains(&rating) {
+        runtime::revert(ApiError::User(28));
+    }
+    let token_id = completion_token_of(caller, media_id.clone());
+    let mut ratings = get_token_ratings();
+    let old = ratings.insert(token_id, rating);
+    set_token_ratings(ratings);
+    update_media_rating(media_id, old, Some(rating));
+}
+
+#[no_mangle]
+pub extern "C" fn review_media() {
+    require_not_paused(PAUSE_METADATA);
+    let caller = caller_key();
+    let media_id: String = runtime::get_named_arg(ARG_MEDIA_ID);
+    let review: String = runtime::get_named_arg(ARG_REVIEW);
+    if review.len() > MAX_REVIEW_LEN {
+        runtime::revert(ApiError::User(66));
+    }
+    let token_id = completion_token_of(caller, media_id);
+    let mut reviews = get_token_reviews();
+    if review.is_empty() {
+        reviews.remove(&token_id);
+    } else {
+        reviews.insert(token_id, review);
+    }
+    set_token_reviews(reviews);
+}
+
+#[no_mangle]
+pub extern "C" fn token_review() {
+    let token_id: U256 = runtime::get_named_arg(ARG_TOKEN_ID);
+    if !get_token_owner().contains_key(&token_id) {
+        runtime::revert(ApiError::User(9));
+    }
+    let rating = get_token_ratings().get(&token_id).copied().unwrap_or(0u8);
+    let review = get_token_reviews().get(&token_id).cloned().unwrap_or_default();
+    runtime::ret(CLValue::from_t((rating, review)).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn media_rating() {
+    let media_id: String = runtime::get_named_arg(ARG_MEDIA_ID);
+    let rating = get_media_ratings()
+        .get(&media_id)
+        .cloned()
+        .unwrap_or_else(|| (0u64, 0u64, vec![0u64; RATING_MAX as usize]));
+    runtime::ret(CLValue::from_t(rating).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn set_achievement() {
+    require_owner();
+    let achievement_id: String = runtime::get_named_arg(ARG_ACHIEVEMENT_ID);
+    let rule: u8 = runtime::get_named_arg(ARG_RULE);
+    let kind: u8 = runtime::get_named_arg(ARG_KIND);
+    let threshold: u64 = runtime::get_named_arg(ARG_THRESHOLD);
+    let uri: String = runtime::get_named_arg(ARG_URI);
+    let valid = match rule {
+        ACHIEVEMENT_TOTAL_COMPLETIONS | ACHIEVEMENT_KIND_COUNT => threshold > 0,
+        ACHIEVEMENT_ALL_KINDS | ACHIEVEMENT_FIRST_COMPLETER => true,
+        _ => false,
+    };
+    if achievement_id.is_empty() || !valid {
+        runtime::revert(ApiError::User(30));
+    }
+    let mut achievements = get_achievements();
+    achievements.insert(achievement_id, ((rule, kind, threshold), uri));
+    set_achievements(achievements);
+}
+
+#[no_mangle]
+pub extern "C" fn remove_achievement() {
+    require_owner();
+    let achievement_id: String = runtime::get_named_arg(ARG_ACHIEVEMENT_ID);
+    let mut achievements = get_achievements();
+    if achievements.remove(&achievement_id).is_none() {
+        runtime::revert(ApiError::User(31));
+    }
+    set_achievements(achievements);
+}
+
+#[no_mangle]
+pub extern "C" fn user_achievements() {
+    let user: Key = runtime::get_named_arg(ARG_USER);
+    let achievements = get_user_achievements().get(&user).cloned().unwrap_or_default();
+    runtime::ret(CLValue::from_t(achievements).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn token_info() {
+    let token_id: U256 = runtime::get_named_arg(ARG_TOKEN_ID);
+    let info = get_token_info_map()
+        .get(&token_id)
+        .copied()
+        .unwrap_or_revert_with(ApiError::User(9));
+    runtime::ret(CLValue::from_t(info).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn set_first_edition_limit() {
+    require_not_paused(PAUSE_METADATA);
+    require_owner();
+    let limit: u64 = runtime::get_named_arg(ARG_FIRST_EDITION_LIMIT);
+    storage::write(get_or_init_uref::<u64>(FIRST_EDITION_LIMIT_KEY), limit);
 }
 
-fn hex_byte(b: u8) -> String {
-    const HEX: &[u8; 16] = b"0123456789abcdef";
-    let mut s = String::with_capacity(2);
-    s.push(HEX[(b >> 4) as usize] as char);
-    s.push(HEX[(b & 0x0f) as usize] as char);
-    s
+#[no_mangle]
+pub extern "C" fn first_edition_limit() {
+    runtime::ret(CLValue::from_t(get_first_edition_limit()).unwrap_or_revert());
 }
 
-fn require_token_owner(token_id: U256, user: Key) {
-    let token_owner = get_token_owner();
-    match token_owner.get(&token_id) {
-        Some(owner) if *owner == user => {}
-        _ => runtime::revert(ApiError::User(3)),
+#[no_mangle]
+pub extern "C" fn set_metadata_policy() {
+    require_not_paused(PAUSE_METADATA);
+    require_owner();
+    let policy: u8 = runtime::get_named_arg(ARG_POLICY);
+    if policy != METADATA_IMMUTABLE
+        && policy != METADATA_OWNER_UPDATABLE
+        && policy != METADATA_BACKEND_UPDATABLE
+    {
+        runtime::revert(ApiError::User(32));
     }
+    storage::write(get_or_init_uref::<u8>(METADATA_POLICY_KEY), policy);
 }
 
-fn add_media_completer(user: Key, media_id: String) {
-    let mut media_completers = get_media_completers();
-    let mut index_map = get_media_completer_index_plus_one();
-
-    let key = (media_id.clone(), user);
-    if index_map.get(&key).copied().unwrap_or(U256::zero()) != U256::zero() {
-        return;
+#[no_mangle]
+pub extern "C" fn set_token_metadata() {
+    require_not_paused(PAUSE_METADATA);
+    let caller = caller_key();
+    let token_id: U256 = runtime::get_named_arg(ARG_TOKEN_ID);
+    let platform: String = runtime::get_named_arg(ARG_PLATFORM);
+    let watch_time: u64 = runtime::get_named_arg(ARG_WATCH_TIME);
+    let token_owner = get_token_owner()
+        .get(&token_id)
+        .copied()
+        .unwrap_or_revert_with(ApiError::User(9));
+
+    let exists = get_token_metadata_entry(token_id).is_some();
+    let is_backend = caller == get_backend();
+    let policy = get_metadata_policy();
+    let allowed = match policy {
+        METADATA_OWNER_UPDATABLE => caller == token_owner || (!exists && is_backend),
+        METADATA_BACKEND_UPDATABLE => is_backend,
+        _ => !exists && is_backend,
+    };
+    if !allowed {
+        if exists && policy == METADATA_IMMUTABLE {
+            runtime::revert(ApiError::User(33));
+        }
+        runtime::revert(ApiError::User(34));
     }
-
-    let list = media_completers.entry(media_id.clone()).or_insert_with(Vec::new);
-    list.push(user);
-    index_map.insert(key, U256::from(list.len() as u64));
-
-    set_media_completers(media_completers);
-    set_media_completer_index_plus_one(index_map);
+    set_token_metadata_entry(token_id, (platform, watch_time));
 }
 
-fn remove_media_completer(user: Key, media_id: String) {
-    let mut media_completers = get_media_completers();
-    let mut index_map = get_media_completer_index_plus_one();
+#[no_mangle]
+pub extern "C" fn token_metadata() {
+    let token_id: U256 = runtime::get_named_arg(ARG_TOKEN_ID);
+    if !get_token_owner().contains_key(&token_id) {
+        runtime::revert(ApiError::User(9));
+    }
+    let media_id = get_token_media_id().get(&token_id).cloned().unwrap_or_default();
+    let (kind, _exists, (uri, name)) = get_media()
+        .get(&media_id)
+        .cloned()
+        .unwrap_or((0u8, false, (String::new(), String::new())));
+    let (edition, completed_at, first_edition) = get_token_info_map()
+        .get(&token_id)
+        .copied()
+        .unwrap_or((0u64, 0u64, false));
+    let (platform, watch_time) = get_token_metadata_entry(token_id).unwrap_or_default();
+    let rating = get_token_ratings().get(&token_id).copied().unwrap_or(0u8);
+    let (completion_count, last_completed_at) = get_token_owner()
+        .get(&token_id)
+        .and_then(|owner| get_completion_counts().get(&(*owner, media_id.clone())).copied())
+        .unwrap_or((1u64, completed_at));
+    let edition_uri = get_token_campaign_uris().get(&token_id).cloned();
+    let special_edition = edition_uri.is_some();
+
+    let json = format!(
+        "{{\"token_id\":\"{}\",\"media_id\":\"{}\",\"name\":\"{}\",\"kind\":{},\"uri\":\"{}\",\"edition\":{},\"first_edition\":{},\"special_edition\":{},\"special_edition_uri\":\"{}\",\"completed_at\":{},\"completion_count\":{},\"last_completed_at\":{},\"platform\":\"{}\",\"watch_time\":{},\"rating\":{}}}",
+        token_id,
+        media_id,
+        json_escape(&name),
+        kind,
+        json_escape(&uri),
+        edition,
+        first_edition,
+        special_edition,
+        json_escape(&edition_uri.unwrap_or_default()),
+        completed_at,
+        completion_count,
+        last_completed_at,
+        json_escape(&platform),
+        watch_time,
+        rating,
+    );
+    runtime::ret(CLValue::from_t(json).unwrap_or_revert());
+}
 
-    let key = (media_id.clone(), user);
-    let index_plus_one = index_map.get(&key).copied().unwrap_or(U256::zero());
-    if index_plus_one == U256::zero() {
-        return;
+#[no_mangle]
+pub extern "C" fn set_kind_base_uri() {
+    require_not_paused(PAUSE_METADATA);
+    require_owner();
+    let kind: u8 = runtime::get_named_arg(ARG_KIND);
+    let base: String = runtime::get_named_arg(ARG_BASE_URI);
+    let mut kind_base_uris = get_kind_base_uris();
+    if base.is_empty() {
+        kind_base_uris.remove(&kind);
+    } else {
+        kind_base_uris.insert(kind, base);
     }
+    set_kind_base_uris(kind_base_uris);
+}
 
-    let list = media_completers.entry(media_id.clone()).or_insert_with(Vec::new);
-    let idx = (index_plus_one.as_u64() as usize).saturating_sub(1);
-    let last_idx = list.len().saturating_sub(1);
+#[no_mangle]
+pub extern "C" fn kind_base_uri() {
+    let kind: u8 = runtime::get_named_arg(ARG_KIND);
+    let base = get_kind_base_uris().get(&kind).cloned().unwrap_or_default();
+    runtime::ret(CLValue::from_t(base).unwrap_or_revert());
+}
 
-    if idx < list.len() {
-        if idx != last_idx {
-            let last = list[last_idx];
-            list[idx] = last;
-            index_map.insert((media_id.clone(), last), U256::from(idx as u64 + 1));
-        }
-        list.pop();
+#[no_mangle]
+pub extern "C" fn revoke_completion() {
+    require_not_paused(PAUSE_BURNING);
+    require_owner_or_backend();
+    let user: Key = runtime::get_named_arg(ARG_USER);
+    let media_id: String = runtime::get_named_arg(ARG_MEDIA_ID);
+    let reason_code: u8 = runtime::get_named_arg(ARG_REASON_CODE);
+    let cooldown: u64 = runtime::get_named_arg(ARG_COOLDOWN);
+    let mut revealed = get_revealed_completions();
+    let was_revealed = revealed.remove(&(user, media_id.clone())).is_some();
+    if was_revealed {
+        set_revealed_completions(revealed);
+    }
+    let token_id = get_completion_token_id()
+        .get(&(user, media_id.clone()))
+        .copied()
+        .unwrap_or(U256::zero());
+    if token_id != U256::zero() {
+        burn_internal(user, token_id);
+    } else if was_revealed {
+        leave_media_memberships_internal(user, media_id.clone());
+    } else {
+        runtime::revert(ApiError::User(29));
     }
 
-    index_map.remove(&key);
+    let now = u64::from(runtime::get_blocktime());
+    revoke_challenge_progress_internal(user, &media_id, now);
+    let mut revocations = get_revocations();
+    revocations.insert((user, media_id), (reason_code, now, now.saturating_add(cooldown)));
+    set_revocations(revocations);
+}
 
-    set_media_completers(media_completers);
-    set_media_completer_index_plus_one(index_map);
+#[no_mangle]
+pub extern "C" fn revocation_info() {
+    let user: Key = runtime::get_named_arg(ARG_USER);
+    let media_id: String = runtime::get_named_arg(ARG_MEDIA_ID);
+    let info = get_revocations()
+        .get(&(user, media_id))
+        .copied()
+        .unwrap_or_revert_with(ApiError::User(37));
+    runtime::ret(CLValue::from_t(info).unwrap_or_revert());
 }
 
-fn join_group_internal(user: Key, media_id: String) {
-    let mut group_members = get_group_members_map();
-    let mut group_index = get_group_index_plus_one();
+#[no_mangle]
+pub extern "C" fn set_repeat_completions() {
+    require_owner();
+    let enabled: bool = runtime::get_named_arg(ARG_ENABLED);
+    storage::write(get_or_init_uref::<bool>(REPEAT_COMPLETIONS_KEY), enabled);
+}
 
-    let key = (media_id.clone(), user);
-    if group_index.get(&key).copied().unwrap_or(U256::zero()) != U256::zero() {
-        return;
-    }
+#[no_mangle]
+pub extern "C" fn completion_count_for() {
+    let user: Key = runtime::get_named_arg(ARG_USER);
+    let media_id: String = runtime::get_named_arg(ARG_MEDIA_ID);
+    let count = get_completion_counts()
+        .get(&(user, media_id))
+        .map(|(count, _last_completed_at)| *count)
+        .unwrap_or(0u64);
+    runtime::ret(CLValue::from_t(count).unwrap_or_revert());
+}
 
-    let list = group_members.entry(media_id.clone()).or_insert_with(Vec::new);
-    list.push(user);
-    group_index.insert(key, U256::from(list.len() as u64));
+#[no_mangle]
+pub extern "C" fn pause() {
+    require_owner();
+    let scopes: u8 = runtime::get_named_arg(ARG_SCOPES);
+    require_valid_scopes(scopes);
+    set_paused_scopes(get_paused_scopes() | scopes);
+}
 
-    set_group_members_map(group_members);
-    set_group_index_plus_one(group_index);
+#[no_mangle]
+pub extern "C" fn unpause() {
+    require_owner();
+    let scopes: u8 = runtime::get_named_arg(ARG_SCOPES);
+    require_valid_scopes(scopes);
+    set_paused_scopes(get_paused_scopes() & !scopes);
 }
 
-fn remove_group_member_internal(user: Key, media_id: String) {
-    let mut group_members = get_group_members_map();
-    let mut group_index = get_group_index_plus_one();
+#[no_mangle]
+pub extern "C" fn paused_scopes() {
+    runtime::ret(CLValue::from_t(get_paused_scopes()).unwrap_or_revert());
+}
 
-    let key = (media_id.clone(), user);
-    let index_plus_one = group_index.get(&key).copied().unwrap_or(U256::zero());
-    if index_plus_one == U256::zero() {
-        return;
-    }
+#[no_mangle]
+pub extern "C" fn set_mint_limits() {
+    require_owner();
+    let window: u64 = runtime::get_named_arg(ARG_WINDOW);
+    let per_user_limit: u64 = runtime::get_named_arg(ARG_PER_USER_LIMIT);
+    let per_minter_limit: u64 = runtime::get_named_arg(ARG_PER_MINTER_LIMIT);
+    storage::write(
+        get_or_init_uref::<(u64, u64, u64)>(MINT_LIMITS_KEY),
+        (window, per_user_limit, per_minter_limit),
+    );
+}
 
-    let list = group_members.entry(media_id.clone()).or_insert_with(Vec::new);
-    let idx = (index_plus_one.as_u64() as usize).saturating_sub(1);
-    let last_idx = list.len().saturating_sub(1);
+#[no_mangle]
+pub extern "C" fn mint_limits() {
+    runtime::ret(CLValue::from_t(get_mint_limits()).unwrap_or_revert());
+}
 
-    if idx < list.len() {
-        if idx != last_idx {
-            let last = list[last_idx];
-            list[idx] = last;
-            group_index.insert((media_id.clone(), last), U256::from(idx as u64 + 1));
-        }
-        list.pop();
+#[no_mangle]
+pub extern "C" fn supports_interface() {
+    let interface_id: String = runtime::get_named_arg(ARG_INTERFACE_ID);
+    let supported = SUPPORTED_INTERFACES.contains(&interface_id.as_str());
+    runtime::ret(CLValue::from_t(supported).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn add_hook() {
+    require_owner();
+    let hook: Key = runtime::get_named_arg(ARG_HOOK);
+    let can_veto: bool = runtime::get_named_arg(ARG_CAN_VETO);
+    if !matches!(hook, Key::Hash(_)) {
+        runtime::revert(ApiError::User(41));
+    }
+    let mut hooks = get_hooks();
+    if !hooks.contains_key(&hook) && hooks.len() >= MAX_HOOKS {
+        runtime::revert(ApiError::User(44));
     }
+    hooks.insert(hook, can_veto);
+    set_hooks(hooks);
+}
 
-    group_index.remove(&key);
+#[no_mangle]
+pub extern "C" fn remove_hook() {
+    require_owner();
+    let hook: Key = runtime::get_named_arg(ARG_HOOK);
+    let mut hooks = get_hooks();
+    if hooks.remove(&hook).is_none() {
+        runtime::revert(ApiError::User(42));
+    }
+    set_hooks(hooks);
+}
 
-    set_group_members_map(group_members);
-    set_group_index_plus_one(group_index);
+#[no_mangle]
+pub extern "C" fn hooks() {
+    let hooks: Vec<Key> = get_hooks().keys().copied().collect();
+    runtime::ret(CLValue::from_t(hooks).unwrap_or_revert());
 }
 
-fn remove_user_token_id(user: Key, token_id: U256) {
-    let mut user_token_ids = get_user_token_ids();
-    let mut index_map = get_user_token_index_plus_one();
+#[no_mangle]
+pub extern "C" fn hook_info() {
+    let hook: Key = runtime::get_named_arg(ARG_HOOK);
+    let can_veto = match get_hooks().get(&hook) {
+        Some(v) => *v,
+        None => runtime::revert(ApiError::User(42)),
+    };
+    runtime::ret(CLValue::from_t(can_veto).unwrap_or_revert());
+}
 
-    let key = (user, token_id);
-    let index_plus_one = index_map.get(&key).copied().unwrap_or(U256::zero());
-    if index_plus_one == U256::zero() {
-        return;
-    }
+#[no_mangle]
+pub extern "C" fn set_hooks_enabled() {
+    require_owner();
+    let enabled: bool = runtime::get_named_arg(ARG_ENABLED);
+    storage::write(get_or_init_uref::<bool>(HOOKS_DISABLED_KEY), !enabled);
+}
 
-    let list = user_token_ids.entry(user).or_insert_with(Vec::new);
-    let idx = (index_plus_one.as_u64() as usize).saturating_sub(1);
-    let last_idx = list.len().saturating_sub(1);
+#[no_mangle]
+pub extern "C" fn hooks_enabled() {
+    runtime::ret(CLValue::from_t(!get_hooks_disabled()).unwrap_or_revert());
+}
 
-    if idx < list.len() {
-        if idx != last_idx {
-            let last = list[last_idx];
-            list[idx] = last;
-            index_map.insert((user, last), U256::from(idx as u64 + 1));
+#[no_mangle]
+pub extern "C" fn set_reward_token() {
+    require_owner();
+    let reward_token: Option<Key> = runtime::get_named_arg(ARG_REWARD_TOKEN);
+    if let Some(token) = reward_token {
+        if !matches!(token, Key::Hash(_)) {
+            runtime::revert(ApiError::User(45));
         }
-        list.pop();
     }
+    storage::write(
+        get_or_init_uref::<Option<Key>>(REWARD_TOKEN_KEY),
+        reward_token,
+    );
+}
 
-    index_map.remove(&key);
-
-    set_user_token_ids(user_token_ids);
-    set_user_token_index_plus_one(index_map);
+#[no_mangle]
+pub extern "C" fn reward_token() {
+    runtime::ret(CLValue::from_t(get_reward_token()).unwrap_or_revert());
 }
 
-fn complete_internal(user: Key, media_id: String) -> U256 {
-    let mut completion = get_completion_token_id();
-    if completion
-        .get(&(user, media_id.clone()))
-        .copied()
-        .unwrap_or(U256::zero())
-        != U256::zero()
-    {
-        runtime::revert(ApiError::User(4));
+#[no_mangle]
+pub extern "C" fn set_kind_reward() {
+    require_owner();
+    let kind: u8 = runtime::get_named_arg(ARG_KIND);
+    let amount: U256 = runtime::get_named_arg(ARG_AMOUNT);
+    let mut table = get_reward_table();
+    if amount.is_zero() {
+        table.remove(&kind);
+    } else {
+        table.insert(kind, amount);
     }
+    set_reward_table(table);
+}
 
-    let token_id = get_next_token_id();
-    set_next_token_id(token_id + U256::one());
-
-    let mut token_owner = get_token_owner();
-    let mut token_media = get_token_media_id();
-    token_owner.insert(token_id, user);
-    token_media.insert(token_id, media_id.clone());
-    set_token_owner(token_owner);
-    set_token_media_id(token_media);
-
-    completion.insert((user, media_id.clone()), token_id);
-    set_completion_token_id(completion);
+#[no_mangle]
+pub extern "C" fn kind_reward() {
+    let kind: u8 = runtime::get_named_arg(ARG_KIND);
+    let amount = get_reward_table().get(&kind).copied().unwrap_or_default();
+    runtime::ret(CLValue::from_t(amount).unwrap_or_revert());
+}
 
-    let mut user_tokens = get_user_token_ids();
-    let mut user_index = get_user_token_index_plus_one();
-    let list = user_tokens.entry(user).or_insert_with(Vec::new);
-    list.push(token_id);
-    user_index.insert((user, token_id), U256::from(list.len() as u64));
-    set_user_token_ids(user_tokens);
-    set_user_token_index_plus_one(user_index);
+#[no_mangle]
+pub extern "C" fn fund_rewards() {
+    require_owner();
+    let amount: U256 = runtime::get_named_arg(ARG_AMOUNT);
+    runtime::call_contract::<()>(
+        reward_token_hash(),
+        "transfer_from",
+        runtime_args! {
+            ARG_OWNER => caller_key(),
+            ARG_RECIPIENT => self_key(),
+            ARG_AMOUNT => amount,
+        },
+    );
+}
 
-    add_media_completer(user, media_id);
+#[no_mangle]
+pub extern "C" fn withdraw_rewards() {
+    require_owner();
+    let amount: U256 = runtime::get_named_arg(ARG_AMOUNT);
+    let recipient: Key = runtime::get_named_arg(ARG_RECIPIENT);
+    runtime::call_contract::<()>(
+        reward_token_hash(),
+        "transfer",
+        runtime_args! { ARG_RECIPIENT => recipient, ARG_AMOUNT => amount },
+    );
+}
 
-    token_id
+#[no_mangle]
+pub extern "C" fn total_rewards_paid() {
+    runtime::ret(CLValue::from_t(get_total_rewards_paid()).unwrap_or_revert());
 }
 
 #[no_mangle]
-pub extern "C" fn compute_media_id() {
-    let kind: u8 = runtime::get_named_arg(ARG_KIND);
-    let uri: String = runtime::get_named_arg(ARG_URI);
-    let name: String = runtime::get_named_arg(ARG_NAME);
-    let media_id = media_id_hex(kind, &uri, &name);
-    runtime::ret(CLValue::from_t(media_id).unwrap_or_revert());
+pub extern "C" fn set_paid_minting() {
+    require_owner();
+    let enabled: bool = runtime::get_named_arg(ARG_ENABLED);
+    storage::write(get_or_init_uref::<bool>(PAID_MINTING_KEY), enabled);
 }
 
 #[no_mangle]
-pub extern "C" fn set_registrar() {
+pub extern "C" fn set_attestor() {
     require_owner();
-    let registrar: Key = runtime::get_named_arg(ARG_REGISTRAR);
-    let allowed: bool = runtime::get_named_arg(ARG_ALLOWED);
-    let mut registrars: BTreeMap<Key, bool> =
-        storage::read(get_or_init_uref::<BTreeMap<Key, bool>>(REGISTRAR_KEY))
-        .unwrap_or_revert_with(ApiError::Read)
-        .unwrap_or_default();
-    registrars.insert(registrar, allowed);
+    let attestor: PublicKey = runtime::get_named_arg(ARG_ATTESTOR);
     storage::write(
-        get_or_init_uref::<BTreeMap<Key, bool>>(REGISTRAR_KEY),
-        registrars,
+        get_or_init_uref::<Option<PublicKey>>(ATTESTOR_KEY),
+        Some(attestor),
     );
 }
 
 #[no_mangle]
-pub extern "C" fn set_backend() {
-    require_owner();
-    let backend: Key = runtime::get_named_arg(ARG_BACKEND);
-    storage::write(get_uref(BACKEND_KEY), backend);
+pub extern "C" fn set_mint_fee() {
+    require_owner();
+    let kind: u8 = runtime::get_named_arg(ARG_KIND);
+    let fee: U512 = runtime::get_named_arg(ARG_FEE);
+    let mut fees = get_mint_fees();
+    if fee.is_zero() {
+        fees.remove(&kind);
+    } else {
+        fees.insert(kind, fee);
+    }
+    set_mint_fees(fees);
+}
+
+#[no_mangle]
+pub extern "C" fn mint_fee() {
+    let kind: u8 = runtime::get_named_arg(ARG_KIND);
+    let fee = get_mint_fees().get(&kind).copied().unwrap_or_default();
+    runtime::ret(CLValue::from_t(fee).unwrap_or_revert());
 }
 
 #[no_mangle]
-pub extern "C" fn complete_and_register_by_external_id() {
-    require_backend();
-    let to: Key = runtime::get_named_arg(ARG_TO);
+pub extern "C" fn complete_with_payment() {
+    require_not_paused(PAUSE_MINTING);
+    if !get_paid_minting_enabled() {
+        runtime::revert(ApiError::User(47));
+    }
+    let caller = caller_key();
     let kind: u8 = runtime::get_named_arg(ARG_KIND);
     let uri: String = runtime::get_named_arg(ARG_URI);
     let name: String = runtime::get_named_arg(ARG_NAME);
+    let expires_at: u64 = runtime::get_named_arg(ARG_EXPIRES_AT);
+    let signature: Bytes = runtime::get_named_arg(ARG_SIGNATURE);
+    let purse: URef = runtime::get_named_arg(ARG_PURSE);
 
+    if u64::from(runtime::get_blocktime()) > expires_at {
+        runtime::revert(ApiError::User(48));
+    }
+    let attestor = get_attestor().unwrap_or_revert_with(ApiError::User(48));
+    let signature: Signature = bytesrepr::deserialize_from_slice(signature.as_slice())
+        .unwrap_or_revert_with(ApiError::User(48));
     let media_id = media_id_hex(kind, &uri, &name);
+    let message = (self_key(), caller, media_id, expires_at)
+        .to_bytes()
+        .unwrap_or_revert();
+    cryptography::verify_signature(&message, &signature, &attestor)
+        .unwrap_or_revert_with(ApiError::User(48));
 
-    let mut media = get_media();
-    let exists = media.get(&media_id).map(|v| v.1).unwrap_or(false);
-    if !exists {
-        media.insert(media_id.clone(), (kind, true, (uri, name)));
-        set_media(media);
+    let mut consumed = get_consumed_attestations();
+    let attestation_id = blake2b_hex(message);
+    if consumed.contains_key(&attestation_id) {
+        runtime::revert(ApiError::User(64));
+    }
+    consumed.insert(attestation_id, expires_at);
+    set_consumed_attestations(consumed);
+
+    let fee = get_mint_fees().get(&kind).copied().unwrap_or_default();
+    if !fee.is_zero() {
+        system::transfer_from_purse_to_purse(purse, get_or_init_fee_purse(), fee, None)
+            .unwrap_or_revert_with(ApiError::User(49));
     }
 
-    let token_id = complete_internal(to, media_id);
+    let token_id = register_and_complete(caller, kind, uri, name);
     runtime::ret(CLValue::from_t(token_id).unwrap_or_revert());
 }
 
 #[no_mangle]
-pub extern "C" fn set_media_uri() {
+pub extern "C" fn withdraw_fees() {
     require_owner();
-    let media_id: String = runtime::get_named_arg(ARG_MEDIA_ID);
-    let uri: String = runtime::get_named_arg(ARG_URI);
-    let mut media = get_media();
-    let item = match media.get_mut(&media_id) {
-        Some(v) if v.1 => v,
-        _ => runtime::revert(ApiError::User(5)),
+    let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+    let treasury: Key = runtime::get_named_arg(ARG_TREASURY);
+    let account = match treasury {
+        Key::Account(account_hash) => account_hash,
+        _ => runtime::revert(ApiError::User(50)),
     };
-    item.2.0 = uri;
-    set_media(media);
+    system::transfer_from_purse_to_account(get_or_init_fee_purse(), account, amount, None)
+        .unwrap_or_revert_with(ApiError::User(49));
 }
 
 #[no_mangle]
-pub extern "C" fn set_media_kind() {
-    require_owner();
+pub extern "C" fn fee_balance() {
+    let balance = runtime::get_key(FEE_PURSE_KEY)
+        .and_then(Key::into_uref)
+        .and_then(system::get_purse_balance)
+        .unwrap_or_default();
+    runtime::ret(CLValue::from_t(balance).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn create_campaign() {
+    require_not_paused(PAUSE_METADATA);
+    require_owner_or_registrar();
     let media_id: String = runtime::get_named_arg(ARG_MEDIA_ID);
-    let kind: u8 = runtime::get_named_arg(ARG_KIND);
-    let mut media = get_media();
-    let item = match media.get_mut(&media_id) {
-        Some(v) if v.1 => v,
-        _ => runtime::revert(ApiError::User(5)),
+    let cap: u64 = runtime::get_named_arg(ARG_CAP);
+    let start_time: u64 = runtime::get_named_arg(ARG_START_TIME);
+    let end_time: u64 = runtime::get_named_arg(ARG_END_TIME);
+    let edition_uri: String = runtime::get_named_arg(ARG_EDITION_URI);
+    if media_id.is_empty() {
+        runtime::revert(ApiError::User(5));
+    }
+    if cap == 0 || end_time <= start_time {
+        runtime::revert(ApiError::User(52));
+    }
+    let now = u64::from(runtime::get_blocktime());
+    let mut campaigns = get_campaigns();
+    if let Some((_counts, (_start, end), _uri)) = campaigns.get(&media_id) {
+        if now < *end {
+            runtime::revert(ApiError::User(53));
+        }
+    }
+    campaigns.insert(
+        media_id,
+        ((caller_key(), cap, 0), (start_time, end_time), edition_uri),
+    );
+    set_campaigns(campaigns);
+}
+
+#[no_mangle]
+pub extern "C" fn campaign_info() {
+    let media_id: String = runtime::get_named_arg(ARG_MEDIA_ID);
+    let campaign = match get_campaigns().get(&media_id) {
+        Some(v) => v.clone(),
+        None => runtime::revert(ApiError::User(54)),
     };
-    item.0 = kind;
-    set_media(media);
+    runtime::ret(CLValue::from_t(campaign).unwrap_or_revert());
 }
 
 #[no_mangle]
-pub extern "C" fn media_info() {
+pub extern "C" fn campaign_remaining() {
     let media_id: String = runtime::get_named_arg(ARG_MEDIA_ID);
-    let media = get_media();
-    let (kind, exists, (uri, name)) = media
-        .get(&media_id)
+    let now = u64::from(runtime::get_blocktime());
+    let remaining = match get_campaigns().get(&media_id) {
+        Some(((_creator, cap, minted), (_start, end), _uri)) if now < *end => cap - minted,
+        Some(_) => 0,
+        None => runtime::revert(ApiError::User(54)),
+    };
+    runtime::ret(CLValue::from_t(remaining).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn create_challenge() {
+    require_owner();
+    let challenge_id: String = runtime::get_named_arg(ARG_CHALLENGE_ID);
+    let rule: u8 = runtime::get_named_arg(ARG_RULE);
+    let kind: u8 = runtime::get_named_arg(ARG_KIND);
+    let required: u64 = runtime::get_named_arg(ARG_REQUIRED);
+    let media_ids: Vec<String> = runtime::get_named_arg(ARG_MEDIA_IDS);
+    let start_time: u64 = runtime::get_named_arg(ARG_START_TIME);
+    let end_time: u64 = runtime::get_named_arg(ARG_END_TIME);
+    require_valid_rule((rule, kind, required), &media_ids);
+    if challenge_id.is_empty() || end_time <= start_time {
+        runtime::revert(ApiError::User(55));
+    }
+    let mut challenges = get_challenges();
+    if challenges.contains_key(&challenge_id) {
+        runtime::revert(ApiError::User(59));
+    }
+    challenges.insert(
+        challenge_id,
+        ((rule, kind, required), (start_time, end_time), media_ids),
+    );
+    set_challenges(challenges);
+}
+
+#[no_mangle]
+pub extern "C" fn challenge_info() {
+    let challenge_id: String = runtime::get_named_arg(ARG_CHALLENGE_ID);
+    let info = get_challenges()
+        .get(&challenge_id)
         .cloned()
-        .unwrap_or((0u8, false, (String::new(), String::new())));
-    runtime::ret(CLValue::from_t((exists, kind, (uri, name))).unwrap_or_revert());
+        .unwrap_or_revert_with(ApiError::User(56));
+    runtime::ret(CLValue::from_t(info).unwrap_or_revert());
 }
 
 #[no_mangle]
-pub extern "C" fn has_completed() {
-    let user: Key = runtime::get_named_arg(ARG_USER);
-    let media_id: String = runtime::get_named_arg(ARG_MEDIA_ID);
-    let completion = get_completion_token_id();
-    let has = completion
-        .get(&(user, media_id))
-        .copied()
-        .unwrap_or(U256::zero())
-        != U256::zero();
-    runtime::ret(CLValue::from_t(has).unwrap_or_revert());
+pub extern "C" fn join_challenge() {
+    require_not_paused(PAUSE_GROUPS);
+    let caller = caller_key();
+    let challenge_id: String = runtime::get_named_arg(ARG_CHALLENGE_ID);
+    let (rule, (start, end), media_ids) = get_challenges()
+        .get(&challenge_id)
+        .cloned()
+        .unwrap_or_revert_with(ApiError::User(56));
+    let now = u64::from(runtime::get_blocktime());
+    if now >= end {
+        runtime::revert(ApiError::User(57));
+    }
+    let mut progress = get_challenge_progress();
+    let key = (challenge_id.clone(), caller);
+    if progress.contains_key(&key) {
+        runtime::revert(ApiError::User(58));
+    }
+
+    let token_media = get_token_media_id();
+    let token_info = get_token_info_map();
+    let media = get_media();
+    let tokens = get_user_token_ids().get(&caller).cloned().unwrap_or_default();
+    let mut counted = Vec::new();
+    for token_id in tokens {
+        let completed_at = token_info.get(&token_id).map(|v| v.1).unwrap_or(0);
+        let media_id = match token_media.get(&token_id) {
+            Some(v) => v.clone(),
+            None => continue,
+        };
+        let kind = media.get(&media_id).map(|v| v.0).unwrap_or(0);
+        if completed_at >= start
+            && completed_at < end
+            && !counted.contains(&media_id)
+            && challenge_counts_media(rule, &media_ids, &media_id, kind)
+        {
+            counted.push(media_id);
+        }
+    }
+    let count = counted.len() as u64;
+    progress.insert(key, counted);
+    set_challenge_progress(progress);
+
+    let mut user_challenges = get_user_challenges();
+    user_challenges.entry(caller).or_default().push(challenge_id.clone());
+    set_user_challenges(user_challenges);
+
+    if count > 0 {
+        update_challenge_leaderboard(challenge_id, caller, count, now);
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn user_token_ids() {
+pub extern "C" fn challenge_progress() {
+    let challenge_id: String = runtime::get_named_arg(ARG_CHALLENGE_ID);
     let user: Key = runtime::get_named_arg(ARG_USER);
-    let user_tokens = get_user_token_ids();
-    let tokens = user_tokens.get(&user).cloned().unwrap_or_default();
-    runtime::ret(CLValue::from_t(tokens).unwrap_or_revert());
+    let (rule, _window, media_ids) = get_challenges()
+        .get(&challenge_id)
+        .cloned()
+        .unwrap_or_revert_with(ApiError::User(56));
+    let progress = get_challenge_progress()
+        .get(&(challenge_id, user))
+        .map(|v| v.len() as u64)
+        .unwrap_or(0);
+    let goal = challenge_goal(rule, &media_ids);
+    runtime::ret(CLValue::from_t((progress, goal, progress >= goal)).unwrap_or_revert());
 }
 
 #[no_mangle]
-pub extern "C" fn can_text() {
-    let from: Key = runtime::get_named_arg(ARG_FROM);
-    let to: Key = runtime::get_named_arg(ARG_TO);
-    let media_id: String = runtime::get_named_arg(ARG_MEDIA_ID);
-    let completion = get_completion_token_id();
-    let from_ok = completion
-        .get(&(from, media_id.clone()))
-        .copied()
-        .unwrap_or(U256::zero())
-        != U256::zero();
-    let to_ok = completion
-        .get(&(to, media_id))
-        .copied()
-        .unwrap_or(U256::zero())
-        != U256::zero();
-    runtime::ret(CLValue::from_t(from_ok && to_ok).unwrap_or_revert());
+pub extern "C" fn challenge_leaderboard() {
+    let challenge_id: String = runtime::get_named_arg(ARG_CHALLENGE_ID);
+    let entries = get_challenge_leaderboards()
+        .get(&challenge_id)
+        .cloned()
+        .unwrap_or_default();
+    let users: Vec<Key> = entries.iter().map(|(user, _, _)| *user).collect();
+    let viewer = caller_key();
+    let joined = get_challenge_progress().contains_key(&(challenge_id, viewer));
+    let visible: BTreeSet<Key> = visible_completers(&users, viewer, joined)
+        .into_iter()
+        .collect();
+    let board: Vec<(Key, u64)> = entries
+        .iter()
+        .filter(|(user, _progress, _reached_at)| visible.contains(user))
+        .map(|(user, progress, _reached_at)| (*user, *progress))
+        .collect();
+    runtime::ret(CLValue::from_t(board).unwrap_or_revert());
 }
 
 #[no_mangle]
-pub extern "C" fn can_join_group() {
+pub extern "C" fn user_stats() {
     let user: Key = runtime::get_named_arg(ARG_USER);
-    let media_id: String = runtime::get_named_arg(ARG_MEDIA_ID);
-    let completion = get_completion_token_id();
-    let has = completion
-        .get(&(user, media_id.clone()))
-        .copied()
-        .unwrap_or(U256::zero())
-        != U256::zero();
-    let group_index = get_group_index_plus_one();
-    let in_group = group_index
-        .get(&(media_id, user))
-        .copied()
-        .unwrap_or(U256::zero())
-        != U256::zero();
-    runtime::ret(CLValue::from_t(has && !in_group).unwrap_or_revert());
+    let stats = get_user_stats_map().get(&user).cloned().unwrap_or_default();
+    runtime::ret(CLValue::from_t(stats).unwrap_or_revert());
 }
 
 #[no_mangle]
-pub extern "C" fn join_group() {
-    let caller = Key::Account(runtime::get_caller());
-    let media_id: String = runtime::get_named_arg(ARG_MEDIA_ID);
-    let completion = get_completion_token_id();
-    let has = completion
-        .get(&(caller, media_id.clone()))
-        .copied()
-        .unwrap_or(U256::zero())
-        != U256::zero();
-    if !has {
-        runtime::revert(ApiError::User(6));
+pub extern "C" fn set_visibility() {
+    require_not_paused(PAUSE_GROUPS);
+    let caller = caller_key();
+    let visibility: u8 = runtime::get_named_arg(ARG_VISIBILITY);
+    if visibility != VISIBILITY_PUBLIC
+        && visibility != VISIBILITY_CO_COMPLETERS
+        && visibility != VISIBILITY_HIDDEN
+    {
+        runtime::revert(ApiError::User(60));
     }
-    join_group_internal(caller, media_id);
+    let mut visibilities = get_visibilities();
+    if visibility == VISIBILITY_PUBLIC {
+        visibilities.remove(&caller);
+    } else {
+        visibilities.insert(caller, visibility);
+    }
+    set_visibilities(visibilities);
 }
 
 #[no_mangle]
-pub extern "C" fn leave_group() {
-    let caller = Key::Account(runtime::get_caller());
-    let media_id: String = runtime::get_named_arg(ARG_MEDIA_ID);
-    let group_index = get_group_index_plus_one();
-    let in_group = group_index
-        .get(&(media_id.clone(), caller))
+pub extern "C" fn visibility() {
+    let user: Key = runtime::get_named_arg(ARG_USER);
+    let visibility = get_visibilities()
+        .get(&user)
         .copied()
-        .unwrap_or(U256::zero())
-        != U256::zero();
-    if !in_group {
-        runtime::revert(ApiError::User(7));
-    }
-    remove_group_member_internal(caller, media_id);
+        .unwrap_or(VISIBILITY_PUBLIC);
+    runtime::ret(CLValue::from_t(visibility).unwrap_or_revert());
 }
 
 #[no_mangle]
-pub extern "C" fn is_group_member() {
+pub extern "C" fn media_completers_page() {
     let media_id: String = runtime::get_named_arg(ARG_MEDIA_ID);
-    let user: Key = runtime::get_named_arg(ARG_USER);
-    let group_index = get_group_index_plus_one();
-    let in_group = group_index
-        .get(&(media_id, user))
-        .copied()
-        .unwrap_or(U256::zero())
-        != U256::zero();
-    runtime::ret(CLValue::from_t(in_group).unwrap_or_revert());
+    let start: U256 = runtime::get_named_arg(ARG_START);
+    let count: U256 = runtime::get_named_arg(ARG_COUNT);
+    let list = get_media_completers()
+        .get(&media_id)
+        .cloned()
+        .unwrap_or_default();
+    let viewer = caller_key();
+    let visible = visible_completers(&list, viewer, is_completed(viewer, media_id));
+    runtime::ret(CLValue::from_t(page_of(&visible, start, count)).unwrap_or_revert());
 }
 
 #[no_mangle]
-pub extern "C" fn group_member_count() {
+pub extern "C" fn media_completer_count() {
     let media_id: String = runtime::get_named_arg(ARG_MEDIA_ID);
-    let group_members = get_group_members_map();
-    let count = group_members
+    let count = get_media_completers()
         .get(&media_id)
         .map(|v| U256::from(v.len() as u64))
         .unwrap_or(U256::zero());
@@ -883,128 +5178,75 @@ pub extern "C" fn group_member_count() {
 }
 
 #[no_mangle]
-pub extern "C" fn group_member_at() {
+pub extern "C" fn compute_commitment() {
+    let user: Key = runtime::get_named_arg(ARG_USER);
     let media_id: String = runtime::get_named_arg(ARG_MEDIA_ID);
-    let index: U256 = runtime::get_named_arg(ARG_INDEX);
-    let idx = index.as_u64() as usize;
-    let group_members = get_group_members_map();
-    let list = group_members.get(&media_id).cloned().unwrap_or_default();
-    if idx >= list.len() {
-        runtime::revert(ApiError::User(8));
-    }
-    runtime::ret(CLValue::from_t(list[idx]).unwrap_or_revert());
-}
-
-#[no_mangle]
-pub extern "C" fn owner_of() {
-    let token_id: U256 = runtime::get_named_arg(ARG_TOKEN_ID);
-    let token_owner = get_token_owner();
-    let owner = token_owner.get(&token_id).copied().unwrap_or_revert();
-    runtime::ret(CLValue::from_t(owner).unwrap_or_revert());
+    let salt: String = runtime::get_named_arg(ARG_SALT);
+    let commitment = commitment_hex(user, &media_id, &salt);
+    runtime::ret(CLValue::from_t(commitment).unwrap_or_revert());
 }
 
 #[no_mangle]
-pub extern "C" fn token_uri() {
-    let token_id: U256 = runtime::get_named_arg(ARG_TOKEN_ID);
-    let token_owner = get_token_owner();
-    if token_owner.get(&token_id).is_none() {
-        runtime::revert(ApiError::User(9));
-    }
-    let token_media = get_token_media_id();
-    let media_id = token_media.get(&token_id).cloned().unwrap_or_default();
-    let media = get_media();
-    let uri = media.get(&media_id).map(|v| v.2.0.clone()).unwrap_or_default();
-    if !uri.is_empty() {
-        runtime::ret(CLValue::from_t(uri).unwrap_or_revert());
+pub extern "C" fn complete_private() {
+    require_not_paused(PAUSE_MINTING);
+    require_backend();
+    let commitment: String = runtime::get_named_arg(ARG_COMMITMENT);
+    if commitment.len() != 64
+        || !commitment
+            .bytes()
+            .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+    {
+        runtime::revert(ApiError::User(61));
     }
-    let base = get_base_uri();
-    if base.is_empty() {
-        runtime::ret(CLValue::from_t(String::new()).unwrap_or_revert());
+    let mut commitments = get_private_commitments();
+    if commitments.contains_key(&commitment) {
+        runtime::revert(ApiError::User(61));
     }
-    runtime::ret(CLValue::from_t(format!("{}{}", base, media_id)).unwrap_or_revert());
-}
-
-#[no_mangle]
-pub extern "C" fn set_base_uri() {
-    require_owner();
-    let base: String = runtime::get_named_arg(ARG_BASE_URI);
-    set_base_uri_internal(base);
+    let minter = caller_key();
+    let now = u64::from(runtime::get_blocktime());
+    enforce_minter_limit(minter, now);
+    commitments.insert(commitment, (now, minter));
+    set_private_commitments(commitments);
 }
 
 #[no_mangle]
-pub extern "C" fn burn() {
-    let token_id: U256 = runtime::get_named_arg(ARG_TOKEN_ID);
-    let caller = Key::Account(runtime::get_caller());
-    require_token_owner(token_id, caller);
-
-    let mut token_owner = get_token_owner();
-    token_owner.remove(&token_id);
-    set_token_owner(token_owner);
-
-    let mut token_media = get_token_media_id();
-    let media_id = token_media.remove(&token_id).unwrap_or_default();
-    set_token_media_id(token_media);
-
-    let mut completion = get_completion_token_id();
-    completion.remove(&(caller, media_id.clone()));
-    set_completion_token_id(completion);
-
-    remove_user_token_id(caller, token_id);
-    remove_media_completer(caller, media_id.clone());
-    remove_group_member_internal(caller, media_id);
+pub extern "C" fn has_commitment() {
+    let commitment: String = runtime::get_named_arg(ARG_COMMITMENT);
+    let has = get_private_commitments().contains_key(&commitment);
+    runtime::ret(CLValue::from_t(has).unwrap_or_revert());
 }
 
 #[no_mangle]
-pub extern "C" fn get_similars_from_tokens() {
-    let token_ids: Vec<U256> = runtime::get_named_arg(ARG_TOKEN_IDS);
-    if token_ids.is_empty() {
-        runtime::ret(CLValue::from_t(Vec::<Key>::new()).unwrap_or_revert());
+pub extern "C" fn prove_completion() {
+    require_not_paused(PAUSE_MINTING);
+    let user: Key = runtime::get_named_arg(ARG_USER);
+    let media_id: String = runtime::get_named_arg(ARG_MEDIA_ID);
+    let salt: String = runtime::get_named_arg(ARG_SALT);
+    let caller = caller_key();
+    if caller != user && caller != get_backend() {
+        runtime::revert(ApiError::User(63));
     }
-
-    let token_owner = get_token_owner();
-    let user = match token_owner.get(&token_ids[0]) {
+    let commitment = commitment_hex(user, &media_id, &salt);
+    let (completed_at, minter) = match get_private_commitments().get(&commitment) {
         Some(v) => *v,
-        None => runtime::revert(ApiError::User(9)),
+        None => runtime::revert(ApiError::User(62)),
     };
-
-    let token_media = get_token_media_id();
-    let media_completers = get_media_completers();
-
-    let mut out_set = BTreeSet::<Key>::new();
-    for token_id in token_ids.iter() {
-        require_token_owner(*token_id, user);
-        let media_id = token_media.get(token_id).cloned().unwrap_or_default();
-        if let Some(completers) = media_completers.get(&media_id) {
-            for candidate in completers.iter() {
-                if *candidate != user {
-                    out_set.insert(*candidate);
-                }
-            }
-        }
+    let mut revealed = get_revealed_completions();
+    if revealed.contains_key(&(user, media_id.clone())) {
+        return;
     }
 
-    let out: Vec<Key> = out_set.into_iter().collect();
-    runtime::ret(CLValue::from_t(out).unwrap_or_revert());
-}
-
-#[no_mangle]
-pub extern "C" fn get_similars_for_token() {
-    let token_id: U256 = runtime::get_named_arg(ARG_TOKEN_ID);
-    let token_owner = get_token_owner();
-    let user = match token_owner.get(&token_id) {
-        Some(v) => *v,
-        None => runtime::revert(ApiError::User(9)),
-    };
-    let token_media = get_token_media_id();
-    let media_id = token_media.get(&token_id).cloned().unwrap_or_default();
-    let media_completers = get_media_completers();
-    let mut out = Vec::new();
-    if let Some(completers) = media_completers.get(&media_id) {
-        for candidate in completers.iter() {
-            if *candidate != user {
-                out.push(*candidate);
-            }
+    let now = u64::from(runtime::get_blocktime());
+    if let Some((_reason, revoked_at, until)) = get_revocations().get(&(user, media_id.clone())) {
+        if now < *until {
+            runtime::revert(ApiError::User(36));
+        }
+        if completed_at <= *revoked_at {
+            runtime::revert(ApiError::User(65));
         }
     }
-    runtime::ret(CLValue::from_t(out).unwrap_or_revert());
+    enforce_minter_user_limit(minter, user, now);
+
+    revealed.insert((user, media_id), completed_at);
+    set_revealed_completions(revealed);
 }
@@ -0,0 +1,83 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{format, string::String, vec};
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    api_error::ApiError, contracts::ContractHash, runtime_args, CLType,
+    EntityEntryPoint as EntryPoint, EntryPointAccess, EntryPointPayment, EntryPointType,
+    EntryPoints, Key, Parameter,
+};
+
+const CONTRACT_NAME: &str = "completion_gate";
+const INTERFACE_COMPLETIONS_V1: &str = "media_nft.completions.v1";
+
+const ARG_MEDIA_NFT: &str = "media_nft";
+const ARG_USER: &str = "user";
+const ARG_MEDIA_ID: &str = "media_id";
+const ARG_INTERFACE_ID: &str = "interface_id";
+
+fn media_nft_hash() -> ContractHash {
+    let media_nft: Key = runtime::get_named_arg(ARG_MEDIA_NFT);
+    let addr = media_nft
+        .into_hash_addr()
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    ContractHash::new(addr)
+}
+
+#[no_mangle]
+pub extern "C" fn enter_club() {
+    let media_nft = media_nft_hash();
+    let user: Key = runtime::get_named_arg(ARG_USER);
+    let media_id: String = runtime::get_named_arg(ARG_MEDIA_ID);
+
+    let supported: bool = runtime::call_contract(
+        media_nft,
+        "supports_interface",
+        runtime_args! { ARG_INTERFACE_ID => String::from(INTERFACE_COMPLETIONS_V1) },
+    );
+    if !supported {
+        runtime::revert(ApiError::User(1));
+    }
+
+    let completed: bool = runtime::call_contract(
+        media_nft,
+        "has_completed",
+        runtime_args! { ARG_USER => user, ARG_MEDIA_ID => media_id },
+    );
+    if !completed {
+        runtime::revert(ApiError::User(2));
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::new(
+        "enter_club",
+        vec![
+            Parameter::new(ARG_MEDIA_NFT, CLType::Key),
+            Parameter::new(ARG_USER, CLType::Key),
+            Parameter::new(ARG_MEDIA_ID, CLType::String),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    let (contract_hash, _contract_version) = storage::new_contract(
+        entry_points,
+        None,
+        Some(String::from(CONTRACT_NAME)),
+        Some(format!("{}_access", CONTRACT_NAME)),
+        None,
+    );
+
+    runtime::put_key(CONTRACT_NAME, contract_hash.into());
+}
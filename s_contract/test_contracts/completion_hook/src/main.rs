@@ -0,0 +1,87 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{format, string::String, vec, vec::Vec};
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    api_error::ApiError, contracts::NamedKeys, CLType, CLValue, EntityEntryPoint as EntryPoint,
+    EntryPointAccess, EntryPointPayment, EntryPointType, EntryPoints, Parameter,
+};
+
+const CONTRACT_NAME: &str = "completion_hook";
+const ACCEPT_KEY: &str = "accept";
+const CALLS_KEY: &str = "calls";
+
+const ARG_ACCEPT: &str = "accept";
+const ARG_USER: &str = "user";
+const ARG_MEDIA_ID: &str = "media_id";
+const ARG_TOKEN_ID: &str = "token_id";
+
+fn record_call() {
+    let calls_uref = runtime::get_key(CALLS_KEY)
+        .and_then(|key| key.into_uref())
+        .unwrap_or_revert_with(ApiError::MissingKey);
+    storage::add(calls_uref, 1u64);
+
+    let accept_uref = runtime::get_key(ACCEPT_KEY)
+        .and_then(|key| key.into_uref())
+        .unwrap_or_revert_with(ApiError::MissingKey);
+    let accept: bool = storage::read(accept_uref)
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default();
+    runtime::ret(CLValue::from_t(accept).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn on_completion() {
+    record_call();
+}
+
+#[no_mangle]
+pub extern "C" fn on_burn() {
+    record_call();
+}
+
+fn hook_params() -> Vec<Parameter> {
+    vec![
+        Parameter::new(ARG_USER, CLType::Key),
+        Parameter::new(ARG_MEDIA_ID, CLType::String),
+        Parameter::new(ARG_TOKEN_ID, CLType::U256),
+    ]
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let accept: bool = runtime::get_named_arg(ARG_ACCEPT);
+
+    let mut entry_points = EntryPoints::new();
+    for name in ["on_completion", "on_burn"] {
+        entry_points.add_entry_point(EntryPoint::new(
+            name,
+            hook_params(),
+            CLType::Bool,
+            EntryPointAccess::Public,
+            EntryPointType::Called,
+            EntryPointPayment::Caller,
+        ));
+    }
+
+    let mut named_keys = NamedKeys::new();
+    named_keys.insert(String::from(ACCEPT_KEY), storage::new_uref(accept).into());
+    named_keys.insert(String::from(CALLS_KEY), storage::new_uref(0u64).into());
+
+    let (contract_hash, _contract_version) = storage::new_contract(
+        entry_points,
+        Some(named_keys),
+        Some(String::from(CONTRACT_NAME)),
+        Some(format!("{}_access", CONTRACT_NAME)),
+        None,
+    );
+
+    runtime::put_key(CONTRACT_NAME, contract_hash.into());
+}
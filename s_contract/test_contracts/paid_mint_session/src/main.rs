@@ -0,0 +1,55 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+use casper_contract::{
+    contract_api::{account, runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    api_error::ApiError, bytesrepr::Bytes, contracts::ContractHash, runtime_args, Key, U256, U512,
+};
+
+const ARG_MEDIA_NFT: &str = "media_nft";
+const ARG_KIND: &str = "kind";
+const ARG_URI: &str = "uri";
+const ARG_NAME: &str = "name";
+const ARG_EXPIRES_AT: &str = "expires_at";
+const ARG_SIGNATURE: &str = "signature";
+const ARG_AMOUNT: &str = "amount";
+const ARG_PURSE: &str = "purse";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let media_nft: Key = runtime::get_named_arg(ARG_MEDIA_NFT);
+    let media_nft = ContractHash::new(
+        media_nft
+            .into_hash_addr()
+            .unwrap_or_revert_with(ApiError::InvalidArgument),
+    );
+    let kind: u8 = runtime::get_named_arg(ARG_KIND);
+    let uri: String = runtime::get_named_arg(ARG_URI);
+    let name: String = runtime::get_named_arg(ARG_NAME);
+    let expires_at: u64 = runtime::get_named_arg(ARG_EXPIRES_AT);
+    let signature: Bytes = runtime::get_named_arg(ARG_SIGNATURE);
+    let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+
+    let purse = system::create_purse();
+    system::transfer_from_purse_to_purse(account::get_main_purse(), purse, amount, None)
+        .unwrap_or_revert();
+
+    runtime::call_contract::<U256>(
+        media_nft,
+        "complete_with_payment",
+        runtime_args! {
+            ARG_KIND => kind,
+            ARG_URI => uri,
+            ARG_NAME => name,
+            ARG_EXPIRES_AT => expires_at,
+            ARG_SIGNATURE => signature,
+            ARG_PURSE => purse,
+        },
+    );
+}
@@ -0,0 +1,64 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    api_error::ApiError,
+    bytesrepr::{self, Bytes, FromBytes, ToBytes},
+    contracts::ContractHash,
+    CLTyped, Key, RuntimeArgs, U256,
+};
+
+const RESULT_KEY: &str = "query_result";
+
+const ARG_MEDIA_NFT: &str = "media_nft";
+const ARG_ENTRY_POINT: &str = "entry_point";
+const ARG_ARGS: &str = "args";
+const ARG_RETURNS: &str = "returns";
+
+const RETURNS_STRING: u8 = 0;
+const RETURNS_KEYS: u8 = 1;
+const RETURNS_KEY: u8 = 2;
+const RETURNS_U256: u8 = 3;
+const RETURNS_LEADERBOARD: u8 = 4;
+const RETURNS_BOOL: u8 = 5;
+
+fn call_and_store<T: CLTyped + FromBytes + ToBytes>(
+    media_nft: ContractHash,
+    entry_point: &str,
+    args: RuntimeArgs,
+) {
+    let result: T = runtime::call_contract(media_nft, entry_point, args);
+    runtime::put_key(RESULT_KEY, storage::new_uref(result).into());
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let media_nft: Key = runtime::get_named_arg(ARG_MEDIA_NFT);
+    let media_nft = ContractHash::new(
+        media_nft
+            .into_hash_addr()
+            .unwrap_or_revert_with(ApiError::InvalidArgument),
+    );
+    let entry_point: String = runtime::get_named_arg(ARG_ENTRY_POINT);
+    let args: Bytes = runtime::get_named_arg(ARG_ARGS);
+    let args: RuntimeArgs = bytesrepr::deserialize_from_slice(args.as_slice())
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let returns: u8 = runtime::get_named_arg(ARG_RETURNS);
+
+    match returns {
+        RETURNS_STRING => call_and_store::<String>(media_nft, &entry_point, args),
+        RETURNS_KEYS => call_and_store::<Vec<Key>>(media_nft, &entry_point, args),
+        RETURNS_KEY => call_and_store::<Key>(media_nft, &entry_point, args),
+        RETURNS_U256 => call_and_store::<U256>(media_nft, &entry_point, args),
+        RETURNS_LEADERBOARD => call_and_store::<Vec<(Key, u64)>>(media_nft, &entry_point, args),
+        RETURNS_BOOL => call_and_store::<bool>(media_nft, &entry_point, args),
+        _ => runtime::revert(ApiError::InvalidArgument),
+    }
+}
@@ -0,0 +1,190 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, format, string::String, vec, vec::Vec};
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    account::AccountHash,
+    api_error::ApiError,
+    bytesrepr::{FromBytes, ToBytes},
+    contracts::{ContractPackageHash, NamedKeys},
+    system::CallerInfo,
+    CLType, CLTyped, CLValue, EntityAddr, EntityEntryPoint as EntryPoint, EntryPointAccess,
+    EntryPointPayment, EntryPointType, EntryPoints, Key, Parameter, URef, U256,
+};
+
+const CONTRACT_NAME: &str = "reward_token";
+const BALANCES_KEY: &str = "balances";
+const ALLOWANCES_KEY: &str = "allowances";
+
+const CALLER_ACCOUNT: u8 = 0;
+const CALLER_CONTRACT_PACKAGE: u8 = 2;
+const CALLER_ENTITY: u8 = 3;
+const CALLER_CONTRACT: u8 = 4;
+
+const ARG_INITIAL_SUPPLY: &str = "initial_supply";
+const ARG_ADDRESS: &str = "address";
+const ARG_OWNER: &str = "owner";
+const ARG_SPENDER: &str = "spender";
+const ARG_RECIPIENT: &str = "recipient";
+const ARG_AMOUNT: &str = "amount";
+
+fn caller_field<T: CLTyped + FromBytes>(caller: &CallerInfo, index: u8) -> Option<T> {
+    caller
+        .get_field_by_index(index)
+        .and_then(|value| value.clone().into_t::<Option<T>>().ok())
+        .flatten()
+}
+
+fn caller_key() -> Key {
+    let caller = runtime::get_immediate_caller().unwrap_or_revert();
+    let key = match caller.kind() {
+        CALLER_ACCOUNT => caller_field::<AccountHash>(&caller, CALLER_ACCOUNT).map(Key::Account),
+        CALLER_ENTITY => {
+            caller_field::<EntityAddr>(&caller, CALLER_ENTITY).map(Key::AddressableEntity)
+        }
+        CALLER_CONTRACT => caller_field::<ContractPackageHash>(&caller, CALLER_CONTRACT_PACKAGE)
+            .map(|package_hash| Key::Hash(package_hash.value())),
+        _ => None,
+    };
+    key.unwrap_or_revert()
+}
+
+fn named_uref(name: &str) -> URef {
+    runtime::get_key(name)
+        .and_then(|key| key.into_uref())
+        .unwrap_or_revert_with(ApiError::MissingKey)
+}
+
+fn read_map<K: Ord + CLTyped + FromBytes + ToBytes>(name: &str) -> BTreeMap<K, U256> {
+    storage::read(named_uref(name))
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_default()
+}
+
+fn move_balance(owner: Key, recipient: Key, amount: U256) {
+    let mut balances: BTreeMap<Key, U256> = read_map(BALANCES_KEY);
+    let from = balances.get(&owner).copied().unwrap_or_default();
+    if from < amount {
+        runtime::revert(ApiError::User(1));
+    }
+    balances.insert(owner, from - amount);
+    let to = balances.get(&recipient).copied().unwrap_or_default();
+    balances.insert(recipient, to + amount);
+    storage::write(named_uref(BALANCES_KEY), balances);
+}
+
+#[no_mangle]
+pub extern "C" fn balance_of() {
+    let address: Key = runtime::get_named_arg(ARG_ADDRESS);
+    let balances: BTreeMap<Key, U256> = read_map(BALANCES_KEY);
+    let balance = balances.get(&address).copied().unwrap_or_default();
+    runtime::ret(CLValue::from_t(balance).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn transfer() {
+    let recipient: Key = runtime::get_named_arg(ARG_RECIPIENT);
+    let amount: U256 = runtime::get_named_arg(ARG_AMOUNT);
+    move_balance(caller_key(), recipient, amount);
+}
+
+#[no_mangle]
+pub extern "C" fn approve() {
+    let spender: Key = runtime::get_named_arg(ARG_SPENDER);
+    let amount: U256 = runtime::get_named_arg(ARG_AMOUNT);
+    let mut allowances: BTreeMap<(Key, Key), U256> = read_map(ALLOWANCES_KEY);
+    allowances.insert((caller_key(), spender), amount);
+    storage::write(named_uref(ALLOWANCES_KEY), allowances);
+}
+
+#[no_mangle]
+pub extern "C" fn transfer_from() {
+    let owner: Key = runtime::get_named_arg(ARG_OWNER);
+    let recipient: Key = runtime::get_named_arg(ARG_RECIPIENT);
+    let amount: U256 = runtime::get_named_arg(ARG_AMOUNT);
+    let mut allowances: BTreeMap<(Key, Key), U256> = read_map(ALLOWANCES_KEY);
+    let key = (owner, caller_key());
+    let allowance = allowances.get(&key).copied().unwrap_or_default();
+    if allowance < amount {
+        runtime::revert(ApiError::User(2));
+    }
+    allowances.insert(key, allowance - amount);
+    storage::write(named_uref(ALLOWANCES_KEY), allowances);
+    move_balance(owner, recipient, amount);
+}
+
+fn entry_point(name: &str, params: Vec<Parameter>, ret: CLType) -> EntryPoint {
+    EntryPoint::new(
+        name,
+        params,
+        ret,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    )
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let initial_supply: U256 = runtime::get_named_arg(ARG_INITIAL_SUPPLY);
+
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(entry_point(
+        "balance_of",
+        vec![Parameter::new(ARG_ADDRESS, CLType::Key)],
+        CLType::U256,
+    ));
+    entry_points.add_entry_point(entry_point(
+        "transfer",
+        vec![
+            Parameter::new(ARG_RECIPIENT, CLType::Key),
+            Parameter::new(ARG_AMOUNT, CLType::U256),
+        ],
+        CLType::Unit,
+    ));
+    entry_points.add_entry_point(entry_point(
+        "approve",
+        vec![
+            Parameter::new(ARG_SPENDER, CLType::Key),
+            Parameter::new(ARG_AMOUNT, CLType::U256),
+        ],
+        CLType::Unit,
+    ));
+    entry_points.add_entry_point(entry_point(
+        "transfer_from",
+        vec![
+            Parameter::new(ARG_OWNER, CLType::Key),
+            Parameter::new(ARG_RECIPIENT, CLType::Key),
+            Parameter::new(ARG_AMOUNT, CLType::U256),
+        ],
+        CLType::Unit,
+    ));
+
+    let mut balances = BTreeMap::new();
+    balances.insert(Key::Account(runtime::get_caller()), initial_supply);
+    let mut named_keys = NamedKeys::new();
+    named_keys.insert(
+        String::from(BALANCES_KEY),
+        storage::new_uref(balances).into(),
+    );
+    named_keys.insert(
+        String::from(ALLOWANCES_KEY),
+        storage::new_uref(BTreeMap::<(Key, Key), U256>::new()).into(),
+    );
+
+    let (contract_hash, _contract_version) = storage::new_contract(
+        entry_points,
+        Some(named_keys),
+        Some(String::from(CONTRACT_NAME)),
+        Some(format!("{}_access", CONTRACT_NAME)),
+        None,
+    );
+
+    runtime::put_key(CONTRACT_NAME, contract_hash.into());
+}
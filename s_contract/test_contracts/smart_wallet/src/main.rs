@@ -0,0 +1,96 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{format, string::String, vec, vec::Vec};
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    api_error::ApiError, contracts::ContractHash, runtime_args, CLType,
+    EntityEntryPoint as EntryPoint, EntryPointAccess, EntryPointPayment, EntryPointType,
+    EntryPoints, Key, Parameter, U256,
+};
+
+const CONTRACT_NAME: &str = "smart_wallet";
+const PACKAGE_NAME: &str = "smart_wallet_package";
+
+const ARG_MEDIA_NFT: &str = "media_nft";
+const ARG_MEDIA_ID: &str = "media_id";
+const ARG_TOKEN_ID: &str = "token_id";
+
+fn media_nft_hash() -> ContractHash {
+    let media_nft: Key = runtime::get_named_arg(ARG_MEDIA_NFT);
+    let addr = media_nft
+        .into_hash_addr()
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    ContractHash::new(addr)
+}
+
+fn forward_media_id(entry_point: &str) {
+    let media_id: String = runtime::get_named_arg(ARG_MEDIA_ID);
+    runtime::call_contract::<()>(
+        media_nft_hash(),
+        entry_point,
+        runtime_args! { ARG_MEDIA_ID => media_id },
+    );
+}
+
+#[no_mangle]
+pub extern "C" fn join_group() {
+    forward_media_id("join_group");
+}
+
+#[no_mangle]
+pub extern "C" fn leave_group() {
+    forward_media_id("leave_group");
+}
+
+#[no_mangle]
+pub extern "C" fn burn() {
+    let token_id: U256 = runtime::get_named_arg(ARG_TOKEN_ID);
+    runtime::call_contract::<()>(
+        media_nft_hash(),
+        "burn",
+        runtime_args! { ARG_TOKEN_ID => token_id },
+    );
+}
+
+fn params(last: Parameter) -> Vec<Parameter> {
+    vec![Parameter::new(ARG_MEDIA_NFT, CLType::Key), last]
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let mut entry_points = EntryPoints::new();
+    for name in ["join_group", "leave_group"] {
+        entry_points.add_entry_point(EntryPoint::new(
+            name,
+            params(Parameter::new(ARG_MEDIA_ID, CLType::String)),
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Called,
+            EntryPointPayment::Caller,
+        ));
+    }
+    entry_points.add_entry_point(EntryPoint::new(
+        "burn",
+        params(Parameter::new(ARG_TOKEN_ID, CLType::U256)),
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+        EntryPointPayment::Caller,
+    ));
+
+    let (contract_hash, _contract_version) = storage::new_contract(
+        entry_points,
+        None,
+        Some(String::from(PACKAGE_NAME)),
+        Some(format!("{}_access", CONTRACT_NAME)),
+        None,
+    );
+
+    runtime::put_key(CONTRACT_NAME, contract_hash.into());
+}
@@ -1,13 +1,40 @@
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+
     use blake2::digest::{Update, VariableOutput};
     use casper_engine_test_support::{
-        ExecuteRequestBuilder, LmdbWasmTestBuilder, DEFAULT_ACCOUNT_ADDR, LOCAL_GENESIS_REQUEST,
+        ChainspecConfig, ExecuteRequestBuilder, LmdbWasmTestBuilder, CHAINSPEC_SYMLINK,
+        DEFAULT_ACCOUNT_ADDR, DEFAULT_PROPOSER_ADDR, LOCAL_GENESIS_REQUEST,
+    };
+    use casper_types::{
+        account::AccountHash,
+        bytesrepr::{Bytes, FromBytes, ToBytes},
+        contracts::ContractHash,
+        crypto, runtime_args, CLTyped, EntityAddr, Key, PublicKey, RuntimeArgs, SecretKey, U256,
+        U512,
     };
-    use casper_types::{contracts::ContractHash, runtime_args, Key};
 
     const CONTRACT_NAME: &str = "media_nft_contract";
     const WASM_FILE: &str = "target/wasm32-unknown-unknown/release/media_nft_contract.wasm";
+    const GATE_CONTRACT_NAME: &str = "completion_gate";
+    const GATE_WASM_FILE: &str = "target/wasm32-unknown-unknown/release/completion_gate.wasm";
+    const HOOK_CONTRACT_NAME: &str = "completion_hook";
+    const HOOK_WASM_FILE: &str = "target/wasm32-unknown-unknown/release/completion_hook.wasm";
+    const TOKEN_CONTRACT_NAME: &str = "reward_token";
+    const TOKEN_WASM_FILE: &str = "target/wasm32-unknown-unknown/release/reward_token.wasm";
+    const PAID_MINT_WASM_FILE: &str =
+        "target/wasm32-unknown-unknown/release/paid_mint_session.wasm";
+    const QUERY_WASM_FILE: &str = "target/wasm32-unknown-unknown/release/query_result.wasm";
+    const RETURNS_STRING: u8 = 0;
+    const RETURNS_KEYS: u8 = 1;
+    const RETURNS_KEY: u8 = 2;
+    const RETURNS_U256: u8 = 3;
+    const RETURNS_LEADERBOARD: u8 = 4;
+    const RETURNS_BOOL: u8 = 5;
+    const WALLET_CONTRACT_NAME: &str = "smart_wallet";
+    const WALLET_PACKAGE_NAME: &str = "smart_wallet_package";
+    const WALLET_WASM_FILE: &str = "target/wasm32-unknown-unknown/release/smart_wallet.wasm";
 
     #[test]
     fn should_install_contract() {
@@ -142,27 +169,2342 @@ mod tests {
         builder.exec(join_group_request).expect_success().commit();
     }
 
-    fn media_id_hex(kind: u8, uri: &str, name: &str) -> String {
-        let mut bytes = Vec::new();
-        bytes.push(kind);
-        bytes.extend_from_slice(uri.as_bytes());
-        bytes.push(0);
-        bytes.extend_from_slice(name.as_bytes());
+    #[test]
+    fn should_create_and_join_room_after_completion() {
+        let mut builder = install_contract();
 
-        let mut digest = vec![0u8; 32];
-        let mut hasher = blake2::Blake2bVar::new(32).unwrap();
-        hasher.update(&bytes);
-        hasher.finalize_variable(&mut digest).unwrap();
-        let mut out = String::with_capacity(64);
-        for b in digest.iter() {
-            out.push_str(&format!("{:02x}", b));
+        let kind = 2u8;
+        let uri = "https://example.com/frieren";
+        let name = "Frieren";
+        let media_id = media_id_hex(kind, uri, name);
+        complete(&mut builder, kind, uri, name);
+
+        call_entry_point(
+            &mut builder,
+            "create_room",
+            runtime_args! {
+                "media_id" => media_id.clone(),
+                "name" => "Spoiler-free",
+                "join_policy" => 0u8,
+            },
+        )
+        .expect_success()
+        .commit();
+
+        let room_id = room_id_hex(&media_id, "Spoiler-free");
+
+        call_entry_point(
+            &mut builder,
+            "join_room",
+            runtime_args! {
+                "room_id" => room_id.clone(),
+            },
+        )
+        .expect_success()
+        .commit();
+
+        call_entry_point(
+            &mut builder,
+            "room_members_page",
+            runtime_args! {
+                "room_id" => room_id.clone(),
+                "start" => U256::zero(),
+                "count" => U256::from(10u64),
+            },
+        )
+        .expect_success()
+        .commit();
+
+        call_entry_point(
+            &mut builder,
+            "leave_room",
+            runtime_args! {
+                "room_id" => room_id,
+            },
+        )
+        .expect_success()
+        .commit();
+    }
+
+    #[test]
+    fn should_not_create_room_without_completion() {
+        let mut builder = install_contract();
+
+        let media_id = media_id_hex(4u8, "https://example.com/dune", "Dune");
+
+        call_entry_point(
+            &mut builder,
+            "create_room",
+            runtime_args! {
+                "media_id" => media_id,
+                "name" => "Book club",
+                "join_policy" => 0u8,
+            },
+        )
+        .expect_failure();
+    }
+
+    #[test]
+    fn should_join_cross_media_group_when_rule_is_met() {
+        let mut builder = install_contract();
+
+        let fellowship = media_id_hex(4u8, "https://example.com/fellowship", "The Fellowship");
+        let towers = media_id_hex(4u8, "https://example.com/two-towers", "The Two Towers");
+        complete(
+            &mut builder,
+            4u8,
+            "https://example.com/fellowship",
+            "The Fellowship",
+        );
+
+        call_entry_point(
+            &mut builder,
+            "create_group",
+            runtime_args! {
+                "name" => "Middle-earth readers",
+                "rule" => 1u8,
+                "kind" => 0u8,
+                "required" => 1u64,
+                "media_ids" => vec![fellowship.clone(), towers.clone()],
+                "join_policy" => 0u8,
+            },
+        )
+        .expect_success()
+        .commit();
+
+        call_entry_point(
+            &mut builder,
+            "create_group",
+            runtime_args! {
+                "name" => "Completionists",
+                "rule" => 0u8,
+                "kind" => 0u8,
+                "required" => 0u64,
+                "media_ids" => vec![fellowship, towers],
+                "join_policy" => 0u8,
+            },
+        )
+        .expect_success()
+        .commit();
+
+        call_entry_point(
+            &mut builder,
+            "join_group",
+            runtime_args! {
+                "media_id" => group_id_hex("Middle-earth readers"),
+            },
+        )
+        .expect_success()
+        .commit();
+
+        call_entry_point(
+            &mut builder,
+            "join_group",
+            runtime_args! {
+                "media_id" => group_id_hex("Completionists"),
+            },
+        )
+        .expect_failure();
+
+        call_entry_point(
+            &mut builder,
+            "create_group",
+            runtime_args! {
+                "name" => "Book readers",
+                "rule" => 2u8,
+                "kind" => 4u8,
+                "required" => 1u64,
+                "media_ids" => Vec::<String>::new(),
+                "join_policy" => 0u8,
+            },
+        )
+        .expect_success()
+        .commit();
+        call_entry_point(
+            &mut builder,
+            "join_group",
+            runtime_args! {
+                "media_id" => group_id_hex("Book readers"),
+            },
+        )
+        .expect_success()
+        .commit();
+
+        let is_member = |builder: &mut LmdbWasmTestBuilder, name: &str| -> bool {
+            query_entry_point(
+                builder,
+                "is_group_member",
+                runtime_args! {
+                    "media_id" => group_id_hex(name),
+                    "user" => Key::Account(*DEFAULT_ACCOUNT_ADDR),
+                },
+                RETURNS_BOOL,
+            )
+        };
+
+        // Burning unrelated media leaves rule groups alone.
+        complete(&mut builder, 1u8, "https://example.com/heat", "Heat");
+        call_entry_point(
+            &mut builder,
+            "burn",
+            runtime_args! { "token_id" => U256::from(2u64) },
+        )
+        .expect_success()
+        .commit();
+        assert!(is_member(&mut builder, "Middle-earth readers"));
+        assert!(is_member(&mut builder, "Book readers"));
+
+        // Burning the media a group depends on removes the member.
+        call_entry_point(
+            &mut builder,
+            "burn",
+            runtime_args! { "token_id" => U256::one() },
+        )
+        .expect_success()
+        .commit();
+        assert!(!is_member(&mut builder, "Middle-earth readers"));
+        assert!(!is_member(&mut builder, "Book readers"));
+    }
+
+    #[test]
+    fn should_join_private_groups_through_invite_and_approval() {
+        let mut builder = install_contract();
+
+        let media_id = media_id_hex(5u8, "https://example.com/berserk", "Berserk");
+        complete(&mut builder, 5u8, "https://example.com/berserk", "Berserk");
+
+        for (name, join_policy) in [("Invite only", 1u8), ("Approval", 2u8)] {
+            call_entry_point(
+                &mut builder,
+                "create_group",
+                runtime_args! {
+                    "name" => name,
+                    "rule" => 0u8,
+                    "kind" => 0u8,
+                    "required" => 0u64,
+                    "media_ids" => vec![media_id.clone()],
+                    "join_policy" => join_policy,
+                },
+            )
+            .expect_success()
+            .commit();
         }
-        out
+
+        let invite_only = group_id_hex("Invite only");
+        call_entry_point(
+            &mut builder,
+            "join_group",
+            runtime_args! {
+                "media_id" => invite_only.clone(),
+            },
+        )
+        .expect_failure();
+
+        call_entry_point(
+            &mut builder,
+            "invite_to_group",
+            runtime_args! {
+                "group_id" => invite_only.clone(),
+                "user" => Key::Account(*DEFAULT_ACCOUNT_ADDR),
+            },
+        )
+        .expect_success()
+        .commit();
+
+        call_entry_point(
+            &mut builder,
+            "accept_invite",
+            runtime_args! {
+                "group_id" => invite_only,
+            },
+        )
+        .expect_success()
+        .commit();
+
+        let approval = group_id_hex("Approval");
+        call_entry_point(
+            &mut builder,
+            "request_to_join",
+            runtime_args! {
+                "group_id" => approval.clone(),
+            },
+        )
+        .expect_success()
+        .commit();
+
+        call_entry_point(
+            &mut builder,
+            "approve_join_request",
+            runtime_args! {
+                "group_id" => approval.clone(),
+                "user" => Key::Account(*DEFAULT_ACCOUNT_ADDR),
+            },
+        )
+        .expect_success()
+        .commit();
+
+        call_entry_point(
+            &mut builder,
+            "reject_join_request",
+            runtime_args! {
+                "group_id" => approval,
+                "user" => Key::Account(*DEFAULT_ACCOUNT_ADDR),
+            },
+        )
+        .expect_failure();
     }
 
-    fn get_contract_hash(builder: &LmdbWasmTestBuilder) -> ContractHash {
-        builder
-            .get_account(*DEFAULT_ACCOUNT_ADDR)
+    #[test]
+    fn should_respect_messaging_preferences_and_blocks() {
+        let mut builder = install_contract();
+
+        complete(&mut builder, 1u8, "https://example.com/arrival", "Arrival");
+
+        call_entry_point(
+            &mut builder,
+            "set_messaging_preference",
+            runtime_args! {
+                "preference" => 2u8,
+            },
+        )
+        .expect_success()
+        .commit();
+
+        call_entry_point(
+            &mut builder,
+            "can_message",
+            runtime_args! {
+                "from" => Key::Account(*DEFAULT_ACCOUNT_ADDR),
+                "to" => Key::Account(*DEFAULT_ACCOUNT_ADDR),
+            },
+        )
+        .expect_success()
+        .commit();
+
+        call_entry_point(
+            &mut builder,
+            "set_messaging_preference",
+            runtime_args! {
+                "preference" => 3u8,
+            },
+        )
+        .expect_failure();
+
+        call_entry_point(
+            &mut builder,
+            "block_user",
+            runtime_args! {
+                "user" => Key::Account(*DEFAULT_ACCOUNT_ADDR),
+            },
+        )
+        .expect_failure();
+    }
+
+    #[test]
+    fn should_require_mutual_follows_when_preferred() {
+        let mut builder = install_contract();
+        let user = Key::Account(*DEFAULT_ACCOUNT_ADDR);
+        let peer = Key::Account(*DEFAULT_PROPOSER_ADDR);
+
+        call_entry_point_as(
+            &mut builder,
+            *DEFAULT_PROPOSER_ADDR,
+            "set_messaging_preference",
+            runtime_args! { "preference" => 1u8 },
+        )
+        .expect_success()
+        .commit();
+
+        let can_message = |builder: &mut LmdbWasmTestBuilder| -> bool {
+            query_entry_point(
+                builder,
+                "can_message",
+                runtime_args! { "from" => user, "to" => peer },
+                RETURNS_BOOL,
+            )
+        };
+        assert!(!can_message(&mut builder));
+
+        call_entry_point(&mut builder, "follow", runtime_args! { "user" => peer })
+            .expect_success()
+            .commit();
+        assert!(!can_message(&mut builder));
+
+        call_entry_point_as(
+            &mut builder,
+            *DEFAULT_PROPOSER_ADDR,
+            "follow",
+            runtime_args! { "user" => user },
+        )
+        .expect_success()
+        .commit();
+        assert!(can_message(&mut builder));
+
+        call_entry_point(&mut builder, "unfollow", runtime_args! { "user" => peer })
+            .expect_success()
+            .commit();
+        assert!(!can_message(&mut builder));
+    }
+
+    #[test]
+    fn should_reject_invalid_follow_operations() {
+        let mut builder = install_contract();
+
+        call_entry_point(
+            &mut builder,
+            "set_follow_gated",
+            runtime_args! {
+                "enabled" => true,
+            },
+        )
+        .expect_success()
+        .commit();
+
+        call_entry_point(
+            &mut builder,
+            "follow",
+            runtime_args! {
+                "user" => Key::Account(*DEFAULT_ACCOUNT_ADDR),
+            },
+        )
+        .expect_failure();
+
+        call_entry_point(
+            &mut builder,
+            "unfollow",
+            runtime_args! {
+                "user" => Key::Hash([7u8; 32]),
+            },
+        )
+        .expect_failure();
+
+        call_entry_point(
+            &mut builder,
+            "followers_page",
+            runtime_args! {
+                "user" => Key::Account(*DEFAULT_ACCOUNT_ADDR),
+                "start" => U256::zero(),
+                "count" => U256::from(10u64),
+            },
+        )
+        .expect_success()
+        .commit();
+    }
+
+    #[test]
+    fn should_rate_and_review_completed_media() {
+        let mut builder = install_contract();
+
+        let media_id = media_id_hex(4u8, "https://example.com/hyperion", "Hyperion");
+
+        call_entry_point(
+            &mut builder,
+            "rate_media",
+            runtime_args! {
+                "media_id" => media_id.clone(),
+                "rating" => 9u8,
+            },
+        )
+        .expect_failure();
+
+        complete(
+            &mut builder,
+            4u8,
+            "https://example.com/hyperion",
+            "Hyperion",
+        );
+
+        call_entry_point(
+            &mut builder,
+            "rate_media",
+            runtime_args! {
+                "media_id" => media_id.clone(),
+                "rating" => 11u8,
+            },
+        )
+        .expect_failure();
+
+        call_entry_point(
+            &mut builder,
+            "rate_media",
+            runtime_args! {
+                "media_id" => media_id.clone(),
+                "rating" => 9u8,
+            },
+        )
+        .expect_success()
+        .commit();
+
+        call_entry_point(
+            &mut builder,
+            "review_media",
+            runtime_args! {
+                "media_id" => media_id.clone(),
+                "review" => "ipfs://bafyreviewhash",
+            },
+        )
+        .expect_success()
+        .commit();
+
+        call_entry_point(
+            &mut builder,
+            "review_media",
+            runtime_args! {
+                "media_id" => media_id.clone(),
+                "review" => "a".repeat(1025),
+            },
+        )
+        .expect_failure();
+        let error = builder.get_error().expect("should have error");
+        assert!(format!("{:?}", error).contains("ApiError::User(66)"));
+
+        let reviews: BTreeMap<U256, String> = query_contract_value(&builder, "token_review");
+        assert_eq!(reviews[&U256::one()], "ipfs://bafyreviewhash");
+
+        call_entry_point(
+            &mut builder,
+            "media_rating",
+            runtime_args! {
+                "media_id" => media_id,
+            },
+        )
+        .expect_success()
+        .commit();
+    }
+
+    #[test]
+    fn should_award_configured_achievements_on_completion() {
+        let mut builder = install_contract();
+
+        call_entry_point(
+            &mut builder,
+            "set_achievement",
+            runtime_args! {
+                "achievement_id" => "first-completion",
+                "rule" => 0u8,
+                "kind" => 0u8,
+                "threshold" => 1u64,
+                "uri" => "https://example.com/badges/first",
+            },
+        )
+        .expect_success()
+        .commit();
+
+        call_entry_point(
+            &mut builder,
+            "set_achievement",
+            runtime_args! {
+                "achievement_id" => "pioneer",
+                "rule" => 3u8,
+                "kind" => 0u8,
+                "threshold" => 0u64,
+                "uri" => "https://example.com/badges/pioneer",
+            },
+        )
+        .expect_success()
+        .commit();
+
+        call_entry_point(
+            &mut builder,
+            "set_achievement",
+            runtime_args! {
+                "achievement_id" => "broken",
+                "rule" => 9u8,
+                "kind" => 0u8,
+                "threshold" => 1u64,
+                "uri" => "",
+            },
+        )
+        .expect_failure();
+
+        complete(&mut builder, 3u8, "https://example.com/saga", "Saga");
+
+        call_entry_point(
+            &mut builder,
+            "user_achievements",
+            runtime_args! {
+                "user" => Key::Account(*DEFAULT_ACCOUNT_ADDR),
+            },
+        )
+        .expect_success()
+        .commit();
+
+        // Achievement tokens live above 2^128, apart from completion token ids.
+        let base = U256::one() << 128;
+        let first = base + U256::one();
+        let pioneer = base + U256::from(2u64);
+        for token_id in [first, pioneer] {
+            let owner: Key = query_entry_point(
+                &mut builder,
+                "owner_of",
+                runtime_args! { "token_id" => token_id },
+                RETURNS_KEY,
+            );
+            assert_eq!(owner, Key::Account(*DEFAULT_ACCOUNT_ADDR));
+        }
+        let uri: String = query_entry_point(
+            &mut builder,
+            "token_uri",
+            runtime_args! { "token_id" => pioneer },
+            RETURNS_STRING,
+        );
+        assert_eq!(uri, "https://example.com/badges/pioneer");
+
+        // Only completion tokens are listed per user; achievements are
+        // listed by `user_achievements`.
+        let user_tokens: BTreeMap<Key, Vec<U256>> =
+            query_contract_value(&builder, "user_token_ids");
+        assert_eq!(
+            user_tokens[&Key::Account(*DEFAULT_ACCOUNT_ADDR)],
+            vec![U256::one()]
+        );
+
+        // Completing the media again after a burn must not make someone else
+        // its first completer.
+        call_entry_point(
+            &mut builder,
+            "burn",
+            runtime_args! { "token_id" => U256::one() },
+        )
+        .expect_success()
+        .commit();
+        call_entry_point(
+            &mut builder,
+            "complete_and_register_by_external_id",
+            runtime_args! {
+                "to" => Key::Account(*DEFAULT_PROPOSER_ADDR),
+                "kind" => 3u8,
+                "uri" => "https://example.com/saga",
+                "name" => "Saga",
+            },
+        )
+        .expect_success()
+        .commit();
+
+        let owner: Key = query_entry_point(
+            &mut builder,
+            "owner_of",
+            runtime_args! { "token_id" => base + U256::from(3u64) },
+            RETURNS_KEY,
+        );
+        assert_eq!(owner, Key::Account(*DEFAULT_PROPOSER_ADDR));
+        call_entry_point(
+            &mut builder,
+            "owner_of",
+            runtime_args! { "token_id" => base + U256::from(4u64) },
+        )
+        .expect_failure();
+
+        call_entry_point(
+            &mut builder,
+            "burn",
+            runtime_args! { "token_id" => pioneer },
+        )
+        .expect_success()
+        .commit();
+        call_entry_point(
+            &mut builder,
+            "token_uri",
+            runtime_args! { "token_id" => pioneer },
+        )
+        .expect_failure();
+    }
+
+    #[test]
+    fn should_record_edition_info_for_completion_tokens() {
+        let mut builder = install_contract();
+
+        let limit: u64 = query_contract_value(&builder, "first_edition_limit");
+        assert_eq!(limit, 100);
+        call_entry_point_as(
+            &mut builder,
+            *DEFAULT_PROPOSER_ADDR,
+            "set_first_edition_limit",
+            runtime_args! { "first_edition_limit" => 1u64 },
+        )
+        .expect_failure();
+        call_entry_point(
+            &mut builder,
+            "set_first_edition_limit",
+            runtime_args! { "first_edition_limit" => 1u64 },
+        )
+        .expect_success()
+        .commit();
+
+        complete(&mut builder, 1u8, "https://example.com/heat", "Heat");
+        call_entry_point(
+            &mut builder,
+            "complete_and_register_by_external_id",
+            runtime_args! {
+                "to" => Key::Account(*DEFAULT_PROPOSER_ADDR),
+                "kind" => 1u8,
+                "uri" => "https://example.com/heat",
+                "name" => "Heat",
+            },
+        )
+        .expect_success()
+        .commit();
+
+        let token_info: BTreeMap<U256, (u64, u64, bool)> =
+            query_contract_value(&builder, "token_info");
+        let first = token_info[&U256::one()];
+        let second = token_info[&U256::from(2u64)];
+        assert_eq!((first.0, first.2), (1, true));
+        assert_eq!((second.0, second.2), (2, false));
+
+        call_entry_point(
+            &mut builder,
+            "token_info",
+            runtime_args! {
+                "token_id" => U256::one(),
+            },
+        )
+        .expect_success()
+        .commit();
+
+        call_entry_point(
+            &mut builder,
+            "token_info",
+            runtime_args! {
+                "token_id" => U256::from(3u64),
+            },
+        )
+        .expect_failure();
+    }
+
+    #[test]
+    fn should_apply_metadata_update_policy() {
+        let mut builder = install_contract();
+
+        complete(
+            &mut builder,
+            6u8,
+            "https://example.com/severance",
+            "Severance",
+        );
+
+        let set_metadata_args = runtime_args! {
+            "token_id" => U256::one(),
+            "platform" => "appletv",
+            "watch_time" => 32_400u64,
+        };
+
+        call_entry_point(
+            &mut builder,
+            "set_token_metadata",
+            set_metadata_args.clone(),
+        )
+        .expect_success()
+        .commit();
+
+        call_entry_point(
+            &mut builder,
+            "set_token_metadata",
+            set_metadata_args.clone(),
+        )
+        .expect_failure();
+
+        call_entry_point(
+            &mut builder,
+            "set_metadata_policy",
+            runtime_args! {
+                "policy" => 2u8,
+            },
+        )
+        .expect_success()
+        .commit();
+
+        call_entry_point(&mut builder, "set_token_metadata", set_metadata_args)
+            .expect_success()
+            .commit();
+
+        call_entry_point(
+            &mut builder,
+            "token_metadata",
+            runtime_args! {
+                "token_id" => U256::one(),
+            },
+        )
+        .expect_success()
+        .commit();
+    }
+
+    #[test]
+    fn should_resolve_token_uri_from_kind_template() {
+        let mut builder = install_contract();
+
+        call_entry_point(
+            &mut builder,
+            "set_kind_base_uri",
+            runtime_args! {
+                "kind" => 4u8,
+                "base_uri" => "https://books.example.com/{kind}/{media_id}/{token_id}.json",
+            },
+        )
+        .expect_success()
+        .commit();
+
+        complete(&mut builder, 4u8, "", "Piranesi");
+
+        let uri: String = query_entry_point(
+            &mut builder,
+            "token_uri",
+            runtime_args! {
+                "token_id" => U256::one(),
+            },
+            RETURNS_STRING,
+        );
+        assert_eq!(
+            uri,
+            format!(
+                "https://books.example.com/4/{}/1.json",
+                media_id_hex(4u8, "", "Piranesi")
+            )
+        );
+    }
+
+    #[test]
+    fn should_render_svg_token_uri_without_external_uri() {
+        let mut builder = install_contract();
+
+        complete(&mut builder, 2u8, "", "Mushishi");
+
+        let uri: String = query_entry_point(
+            &mut builder,
+            "token_uri",
+            runtime_args! {
+                "token_id" => U256::one(),
+            },
+            RETURNS_STRING,
+        );
+        let payload = uri
+            .strip_prefix("data:image/svg+xml;base64,")
+            .expect("should be an SVG data URI");
+        let svg = String::from_utf8(base64::decode(payload).expect("should be base64"))
+            .expect("should be UTF-8");
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains(">Mushishi</text>"));
+        assert!(svg.contains("Edition #1"));
+    }
+
+    #[test]
+    fn should_revoke_completion_and_enforce_cooldown() {
+        let mut builder = install_contract();
+
+        let kind = 1u8;
+        let uri = "https://example.com/tenet";
+        let name = "Tenet";
+        let media_id = media_id_hex(kind, uri, name);
+        complete(&mut builder, kind, uri, name);
+
+        call_entry_point(
+            &mut builder,
+            "revoke_completion",
+            runtime_args! {
+                "user" => Key::Account(*DEFAULT_ACCOUNT_ADDR),
+                "media_id" => media_id.clone(),
+                "reason_code" => 1u8,
+                "cooldown" => u64::MAX,
+            },
+        )
+        .expect_success()
+        .commit();
+
+        call_entry_point(
+            &mut builder,
+            "revocation_info",
+            runtime_args! {
+                "user" => Key::Account(*DEFAULT_ACCOUNT_ADDR),
+                "media_id" => media_id,
+            },
+        )
+        .expect_success()
+        .commit();
+
+        call_entry_point(
+            &mut builder,
+            "complete_and_register_by_external_id",
+            runtime_args! {
+                "to" => Key::Account(*DEFAULT_ACCOUNT_ADDR),
+                "kind" => kind,
+                "uri" => uri,
+                "name" => name,
+            },
+        )
+        .expect_failure();
+    }
+
+    #[test]
+    fn should_count_repeat_completions_when_enabled() {
+        let mut builder = install_contract();
+
+        let kind = 6u8;
+        let uri = "https://example.com/the-wire";
+        let name = "The Wire";
+        let media_id = media_id_hex(kind, uri, name);
+        let complete_args = runtime_args! {
+            "to" => Key::Account(*DEFAULT_ACCOUNT_ADDR),
+            "kind" => kind,
+            "uri" => uri,
+            "name" => name,
+        };
+
+        complete(&mut builder, kind, uri, name);
+
+        call_entry_point(
+            &mut builder,
+            "complete_and_register_by_external_id",
+            complete_args.clone(),
+        )
+        .expect_failure();
+
+        call_entry_point(
+            &mut builder,
+            "set_repeat_completions",
+            runtime_args! {
+                "enabled" => true,
+            },
+        )
+        .expect_success()
+        .commit();
+
+        call_entry_point(
+            &mut builder,
+            "complete_and_register_by_external_id",
+            complete_args,
+        )
+        .expect_success()
+        .commit();
+
+        call_entry_point(
+            &mut builder,
+            "completion_count_for",
+            runtime_args! {
+                "user" => Key::Account(*DEFAULT_ACCOUNT_ADDR),
+                "media_id" => media_id,
+            },
+        )
+        .expect_success()
+        .commit();
+    }
+
+    #[test]
+    fn should_block_minting_while_minting_scope_is_paused() {
+        let mut builder = install_contract();
+
+        let complete_args = runtime_args! {
+            "to" => Key::Account(*DEFAULT_ACCOUNT_ADDR),
+            "kind" => 3u8,
+            "uri" => "https://example.com/watchmen",
+            "name" => "Watchmen",
+        };
+
+        call_entry_point(
+            &mut builder,
+            "pause",
+            runtime_args! {
+                "scopes" => 1u8,
+            },
+        )
+        .expect_success()
+        .commit();
+
+        call_entry_point(
+            &mut builder,
+            "complete_and_register_by_external_id",
+            complete_args.clone(),
+        )
+        .expect_failure();
+
+        call_entry_point(
+            &mut builder,
+            "unpause",
+            runtime_args! {
+                "scopes" => 1u8,
+            },
+        )
+        .expect_success()
+        .commit();
+
+        call_entry_point(
+            &mut builder,
+            "complete_and_register_by_external_id",
+            complete_args,
+        )
+        .expect_success()
+        .commit();
+
+        call_entry_point(
+            &mut builder,
+            "pause",
+            runtime_args! {
+                "scopes" => 16u8,
+            },
+        )
+        .expect_failure();
+    }
+
+    #[test]
+    fn should_enforce_per_minter_mint_quota() {
+        let mut builder = install_contract();
+
+        call_entry_point(
+            &mut builder,
+            "set_mint_limits",
+            runtime_args! {
+                "window" => 86_400_000u64,
+                "per_user_limit" => 0u64,
+                "per_minter_limit" => 1u64,
+            },
+        )
+        .expect_success()
+        .commit();
+
+        complete(
+            &mut builder,
+            5u8,
+            "https://example.com/vagabond",
+            "Vagabond",
+        );
+
+        call_entry_point(
+            &mut builder,
+            "complete_and_register_by_external_id",
+            runtime_args! {
+                "to" => Key::Account(*DEFAULT_ACCOUNT_ADDR),
+                "kind" => 5u8,
+                "uri" => "https://example.com/monster",
+                "name" => "Monster",
+            },
+        )
+        .expect_failure();
+    }
+
+    #[test]
+    fn should_gate_third_party_contract_on_has_completed() {
+        let mut builder = install_contract();
+        let media_nft = Key::from(get_contract_hash(&builder));
+
+        let install_gate = ExecuteRequestBuilder::standard(
+            *DEFAULT_ACCOUNT_ADDR,
+            GATE_WASM_FILE,
+            runtime_args! {},
+        )
+        .build();
+        builder.exec(install_gate).expect_success().commit();
+
+        let gate_hash = get_named_contract_hash(&builder, GATE_CONTRACT_NAME);
+
+        let (kind, uri, name) = (4u8, "https://example.com/dune", "Dune");
+        let media_id = media_id_hex(kind, uri, name);
+        let enter_club = |media_id: &str| {
+            ExecuteRequestBuilder::contract_call_by_hash(
+                *DEFAULT_ACCOUNT_ADDR,
+                gate_hash.into(),
+                "enter_club",
+                runtime_args! {
+                    "media_nft" => media_nft,
+                    "user" => Key::Account(*DEFAULT_ACCOUNT_ADDR),
+                    "media_id" => media_id.to_string(),
+                },
+            )
+            .build()
+        };
+
+        builder.exec(enter_club(&media_id)).expect_failure();
+
+        complete(&mut builder, kind, uri, name);
+
+        builder
+            .exec(enter_club(&media_id))
+            .expect_success()
+            .commit();
+    }
+
+    #[test]
+    fn should_apply_hook_failure_policy_on_completion() {
+        let mut builder = install_contract();
+
+        let install_hook = ExecuteRequestBuilder::standard(
+            *DEFAULT_ACCOUNT_ADDR,
+            HOOK_WASM_FILE,
+            runtime_args! { "accept" => false },
+        )
+        .build();
+        builder.exec(install_hook).expect_success().commit();
+        let hook_hash = get_named_contract_hash(&builder, HOOK_CONTRACT_NAME);
+        let hook = Key::from(hook_hash);
+
+        call_entry_point(
+            &mut builder,
+            "add_hook",
+            runtime_args! {
+                "hook" => Key::Account(*DEFAULT_ACCOUNT_ADDR),
+                "can_veto" => true,
+            },
+        )
+        .expect_failure();
+
+        call_entry_point(
+            &mut builder,
+            "add_hook",
+            runtime_args! {
+                "hook" => hook,
+                "can_veto" => true,
+            },
+        )
+        .expect_success()
+        .commit();
+        assert!(query_entry_point::<bool>(
+            &mut builder,
+            "hook_info",
+            runtime_args! { "hook" => hook },
+            RETURNS_BOOL,
+        ));
+
+        call_entry_point(
+            &mut builder,
+            "complete_and_register_by_external_id",
+            runtime_args! {
+                "to" => Key::Account(*DEFAULT_ACCOUNT_ADDR),
+                "kind" => 6u8,
+                "uri" => "https://example.com/severance",
+                "name" => "Severance",
+            },
+        )
+        .expect_failure();
+        assert!(format!("{:?}", builder.get_error()).contains("ApiError::User(43)"));
+
+        // The owner can switch every hook off while a subscriber misbehaves.
+        call_entry_point(
+            &mut builder,
+            "set_hooks_enabled",
+            runtime_args! { "enabled" => false },
+        )
+        .expect_success()
+        .commit();
+        complete(&mut builder, 6u8, "https://example.com/dark", "Dark");
+        let calls: u64 = query_named_value(&builder, hook_hash, "calls");
+        assert_eq!(calls, 0);
+
+        call_entry_point(
+            &mut builder,
+            "set_hooks_enabled",
+            runtime_args! { "enabled" => true },
+        )
+        .expect_success()
+        .commit();
+        call_entry_point(
+            &mut builder,
+            "add_hook",
+            runtime_args! {
+                "hook" => hook,
+                "can_veto" => false,
+            },
+        )
+        .expect_success()
+        .commit();
+
+        complete(
+            &mut builder,
+            6u8,
+            "https://example.com/severance",
+            "Severance",
+        );
+        let calls: u64 = query_named_value(&builder, hook_hash, "calls");
+        assert_eq!(calls, 1);
+
+        // Repeat completions reuse the existing token and do not notify hooks.
+        call_entry_point(
+            &mut builder,
+            "set_repeat_completions",
+            runtime_args! { "enabled" => true },
+        )
+        .expect_success()
+        .commit();
+        complete(
+            &mut builder,
+            6u8,
+            "https://example.com/severance",
+            "Severance",
+        );
+        let calls: u64 = query_named_value(&builder, hook_hash, "calls");
+        assert_eq!(calls, 1);
+
+        call_entry_point(
+            &mut builder,
+            "remove_hook",
+            runtime_args! { "hook" => hook },
+        )
+        .expect_success()
+        .commit();
+        call_entry_point(
+            &mut builder,
+            "remove_hook",
+            runtime_args! { "hook" => hook },
+        )
+        .expect_failure();
+    }
+
+    #[test]
+    fn should_not_let_hooks_veto_burns_or_revocations() {
+        let mut builder = install_contract();
+
+        complete(&mut builder, 1u8, "https://example.com/heat", "Heat");
+        complete(&mut builder, 1u8, "https://example.com/ronin", "Ronin");
+
+        let install_hook = ExecuteRequestBuilder::standard(
+            *DEFAULT_ACCOUNT_ADDR,
+            HOOK_WASM_FILE,
+            runtime_args! { "accept" => false },
+        )
+        .build();
+        builder.exec(install_hook).expect_success().commit();
+        let hook_hash = get_named_contract_hash(&builder, HOOK_CONTRACT_NAME);
+        call_entry_point(
+            &mut builder,
+            "add_hook",
+            runtime_args! {
+                "hook" => Key::from(hook_hash),
+                "can_veto" => true,
+            },
+        )
+        .expect_success()
+        .commit();
+
+        call_entry_point(
+            &mut builder,
+            "revoke_completion",
+            runtime_args! {
+                "user" => Key::Account(*DEFAULT_ACCOUNT_ADDR),
+                "media_id" => media_id_hex(1u8, "https://example.com/heat", "Heat"),
+                "reason_code" => 1u8,
+                "cooldown" => 0u64,
+            },
+        )
+        .expect_success()
+        .commit();
+        call_entry_point(
+            &mut builder,
+            "burn",
+            runtime_args! { "token_id" => U256::from(2u64) },
+        )
+        .expect_success()
+        .commit();
+
+        let token_owner: BTreeMap<U256, Key> = query_contract_value(&builder, "token_owner");
+        assert!(token_owner.is_empty());
+        let calls: u64 = query_named_value(&builder, hook_hash, "calls");
+        assert_eq!(calls, 2);
+    }
+
+    #[test]
+    fn should_configure_rewards_and_mint_without_reward_token() {
+        let mut builder = install_contract();
+
+        call_entry_point(
+            &mut builder,
+            "set_kind_reward",
+            runtime_args! { "kind" => 2u8, "amount" => U256::from(50u64) },
+        )
+        .expect_success()
+        .commit();
+
+        call_entry_point(
+            &mut builder,
+            "set_reward_token",
+            runtime_args! { "reward_token" => Some(Key::Account(*DEFAULT_ACCOUNT_ADDR)) },
+        )
+        .expect_failure();
+
+        call_entry_point(
+            &mut builder,
+            "fund_rewards",
+            runtime_args! { "amount" => U256::from(1_000u64) },
+        )
+        .expect_failure();
+
+        complete(
+            &mut builder,
+            2u8,
+            "https://example.com/mushishi",
+            "Mushishi",
+        );
+    }
+
+    #[test]
+    fn should_pay_completion_reward_once_per_media() {
+        let mut builder = install_contract();
+        let user = Key::Account(*DEFAULT_ACCOUNT_ADDR);
+        let media_nft_package = Key::Hash(
+            builder
+                .get_contract(get_contract_hash(&builder))
+                .expect("should have contract")
+                .contract_package_hash()
+                .value(),
+        );
+
+        let install_token = ExecuteRequestBuilder::standard(
+            *DEFAULT_ACCOUNT_ADDR,
+            TOKEN_WASM_FILE,
+            runtime_args! { "initial_supply" => U256::from(1_000u64) },
+        )
+        .build();
+        builder.exec(install_token).expect_success().commit();
+        let token_hash = get_named_contract_hash(&builder, TOKEN_CONTRACT_NAME);
+        let balance_of = |builder: &LmdbWasmTestBuilder, owner: Key| {
+            let balances: BTreeMap<Key, U256> = query_named_value(builder, token_hash, "balances");
+            balances.get(&owner).copied().unwrap_or_default()
+        };
+
+        call_entry_point(
+            &mut builder,
+            "set_reward_token",
+            runtime_args! { "reward_token" => Some(Key::from(token_hash)) },
+        )
+        .expect_success()
+        .commit();
+        call_entry_point(
+            &mut builder,
+            "set_kind_reward",
+            runtime_args! { "kind" => 2u8, "amount" => U256::from(50u64) },
+        )
+        .expect_success()
+        .commit();
+
+        let approve = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            token_hash.into(),
+            "approve",
+            runtime_args! { "spender" => media_nft_package, "amount" => U256::from(200u64) },
+        )
+        .build();
+        builder.exec(approve).expect_success().commit();
+        call_entry_point(
+            &mut builder,
+            "fund_rewards",
+            runtime_args! { "amount" => U256::from(200u64) },
+        )
+        .expect_success()
+        .commit();
+        assert_eq!(balance_of(&builder, media_nft_package), U256::from(200u64));
+        assert_eq!(balance_of(&builder, user), U256::from(800u64));
+
+        complete(
+            &mut builder,
+            2u8,
+            "https://example.com/mushishi",
+            "Mushishi",
+        );
+        assert_eq!(balance_of(&builder, media_nft_package), U256::from(150u64));
+        assert_eq!(balance_of(&builder, user), U256::from(850u64));
+        let paid: U256 = query_contract_value(&builder, "total_rewards_paid");
+        assert_eq!(paid, U256::from(50u64));
+
+        let token_ids: BTreeMap<Key, Vec<U256>> = query_contract_value(&builder, "user_token_ids");
+        call_entry_point(
+            &mut builder,
+            "burn",
+            runtime_args! { "token_id" => token_ids[&user][0] },
+        )
+        .expect_success()
+        .commit();
+        complete(
+            &mut builder,
+            2u8,
+            "https://example.com/mushishi",
+            "Mushishi",
+        );
+        assert_eq!(balance_of(&builder, media_nft_package), U256::from(150u64));
+        assert_eq!(balance_of(&builder, user), U256::from(850u64));
+        let paid: U256 = query_contract_value(&builder, "total_rewards_paid");
+        assert_eq!(paid, U256::from(50u64));
+    }
+
+    #[test]
+    fn should_collect_fee_for_attested_mint_and_reject_replay() {
+        let mut builder = install_contract();
+        let user = Key::Account(*DEFAULT_ACCOUNT_ADDR);
+        let media_nft_package = Key::Hash(
+            builder
+                .get_contract(get_contract_hash(&builder))
+                .expect("should have contract")
+                .contract_package_hash()
+                .value(),
+        );
+        let attestor_secret = SecretKey::ed25519_from_bytes([7u8; 32]).unwrap();
+        let attestor = PublicKey::from(&attestor_secret);
+        let fee = U512::from(2_500_000_000u64);
+
+        call_entry_point(
+            &mut builder,
+            "set_attestor",
+            runtime_args! { "attestor" => attestor.clone() },
+        )
+        .expect_success()
+        .commit();
+        call_entry_point(
+            &mut builder,
+            "set_mint_fee",
+            runtime_args! { "kind" => 1u8, "fee" => fee },
+        )
+        .expect_success()
+        .commit();
+        call_entry_point(
+            &mut builder,
+            "set_paid_minting",
+            runtime_args! { "enabled" => true },
+        )
+        .expect_success()
+        .commit();
+
+        let (kind, uri, name) = (1u8, "https://example.com/paprika", "Paprika");
+        let media_id = media_id_hex(kind, uri, name);
+        let message = (media_nft_package, user, media_id, u64::MAX)
+            .to_bytes()
+            .unwrap();
+        let signature = crypto::sign(&message, &attestor_secret, &attestor);
+        let signature = Bytes::from(signature.to_bytes().unwrap());
+        let media_nft = Key::from(get_contract_hash(&builder));
+        let paid_mint = || {
+            ExecuteRequestBuilder::standard(
+                *DEFAULT_ACCOUNT_ADDR,
+                PAID_MINT_WASM_FILE,
+                runtime_args! {
+                    "media_nft" => media_nft,
+                    "kind" => kind,
+                    "uri" => uri,
+                    "name" => name,
+                    "expires_at" => u64::MAX,
+                    "signature" => signature.clone(),
+                    "amount" => fee,
+                },
+            )
+            .build()
+        };
+
+        builder.exec(paid_mint()).expect_success().commit();
+        let fee_purse = builder
+            .get_contract(get_contract_hash(&builder))
+            .expect("should have contract")
+            .named_keys()
+            .get("fee_purse")
+            .and_then(|key| key.into_uref())
+            .expect("should have fee purse");
+        assert_eq!(builder.get_purse_balance(fee_purse), fee);
+
+        let token_ids: BTreeMap<Key, Vec<U256>> = query_contract_value(&builder, "user_token_ids");
+        call_entry_point(
+            &mut builder,
+            "burn",
+            runtime_args! { "token_id" => token_ids[&user][0] },
+        )
+        .expect_success()
+        .commit();
+
+        builder.exec(paid_mint()).expect_failure();
+        let error = builder.get_error().expect("should have error");
+        assert!(format!("{:?}", error).contains("ApiError::User(64)"));
+        assert_eq!(builder.get_purse_balance(fee_purse), fee);
+    }
+
+    #[test]
+    fn should_require_enabled_paid_minting_and_valid_attestation() {
+        let mut builder = install_contract();
+        let purse = builder
+            .get_account(*DEFAULT_ACCOUNT_ADDR)
+            .expect("should have account")
+            .main_purse();
+        let paid_mint_args = || {
+            runtime_args! {
+                "kind" => 1u8,
+                "uri" => "https://example.com/paprika",
+                "name" => "Paprika",
+                "expires_at" => u64::MAX,
+                "signature" => Bytes::from(vec![1u8; 65]),
+                "purse" => purse,
+            }
+        };
+
+        call_entry_point(
+            &mut builder,
+            "set_mint_fee",
+            runtime_args! { "kind" => 1u8, "fee" => U512::from(2_500_000_000u64) },
+        )
+        .expect_success()
+        .commit();
+
+        call_entry_point(&mut builder, "complete_with_payment", paid_mint_args()).expect_failure();
+
+        call_entry_point(
+            &mut builder,
+            "set_paid_minting",
+            runtime_args! { "enabled" => true },
+        )
+        .expect_success()
+        .commit();
+
+        call_entry_point(&mut builder, "complete_with_payment", paid_mint_args()).expect_failure();
+
+        call_entry_point(
+            &mut builder,
+            "withdraw_fees",
+            runtime_args! {
+                "amount" => U512::one(),
+                "treasury" => Key::Hash([7u8; 32]),
+            },
+        )
+        .expect_failure();
+    }
+
+    #[test]
+    fn should_run_sponsored_campaign_for_media() {
+        let mut builder = install_contract();
+        let (kind, uri, name) = (4u8, "https://example.com/piranesi", "Piranesi");
+        let media_id = media_id_hex(kind, uri, name);
+
+        call_entry_point(
+            &mut builder,
+            "create_campaign",
+            runtime_args! {
+                "media_id" => media_id.clone(),
+                "cap" => 0u64,
+                "start_time" => 0u64,
+                "end_time" => u64::MAX,
+                "edition_uri" => "https://example.com/piranesi-special.png",
+            },
+        )
+        .expect_failure();
+
+        call_entry_point(
+            &mut builder,
+            "create_campaign",
+            runtime_args! {
+                "media_id" => media_id.clone(),
+                "cap" => 1u64,
+                "start_time" => 0u64,
+                "end_time" => u64::MAX,
+                "edition_uri" => "https://example.com/piranesi-special.png",
+            },
+        )
+        .expect_success()
+        .commit();
+
+        call_entry_point(
+            &mut builder,
+            "create_campaign",
+            runtime_args! {
+                "media_id" => media_id.clone(),
+                "cap" => 10u64,
+                "start_time" => 0u64,
+                "end_time" => u64::MAX,
+                "edition_uri" => "",
+            },
+        )
+        .expect_failure();
+
+        complete(&mut builder, kind, uri, name);
+
+        call_entry_point(
+            &mut builder,
+            "campaign_remaining",
+            runtime_args! { "media_id" => media_id },
+        )
+        .expect_success()
+        .commit();
+        call_entry_point(
+            &mut builder,
+            "campaign_info",
+            runtime_args! { "media_id" => "unknown" },
+        )
+        .expect_failure();
+    }
+
+    #[test]
+    fn should_track_challenge_progress_for_participants() {
+        let mut builder = install_contract();
+        let challenge_args = || {
+            runtime_args! {
+                "challenge_id" => "two-books",
+                "rule" => 2u8,
+                "kind" => 4u8,
+                "required" => 2u64,
+                "media_ids" => Vec::<String>::new(),
+                "start_time" => 0u64,
+                "end_time" => u64::MAX,
+            }
+        };
+
+        call_entry_point(&mut builder, "create_challenge", challenge_args())
+            .expect_success()
+            .commit();
+        call_entry_point(&mut builder, "create_challenge", challenge_args()).expect_failure();
+
+        complete(&mut builder, 4u8, "https://example.com/gideon", "Gideon");
+
+        call_entry_point(
+            &mut builder,
+            "join_challenge",
+            runtime_args! { "challenge_id" => "two-books" },
+        )
+        .expect_success()
+        .commit();
+        call_entry_point(
+            &mut builder,
+            "join_challenge",
+            runtime_args! { "challenge_id" => "two-books" },
+        )
+        .expect_failure();
+
+        complete(&mut builder, 4u8, "https://example.com/harrow", "Harrow");
+
+        call_entry_point(
+            &mut builder,
+            "challenge_progress",
+            runtime_args! {
+                "challenge_id" => "two-books",
+                "user" => Key::Account(*DEFAULT_ACCOUNT_ADDR),
+            },
+        )
+        .expect_success()
+        .commit();
+        call_entry_point(
+            &mut builder,
+            "join_challenge",
+            runtime_args! { "challenge_id" => "unknown" },
+        )
+        .expect_failure();
+
+        let user = Key::Account(*DEFAULT_ACCOUNT_ADDR);
+        let other = Key::Account(*DEFAULT_PROPOSER_ADDR);
+        call_entry_point(
+            &mut builder,
+            "complete_and_register_by_external_id",
+            runtime_args! {
+                "to" => other,
+                "kind" => 4u8,
+                "uri" => "https://example.com/gideon",
+                "name" => "Gideon",
+            },
+        )
+        .expect_success()
+        .commit();
+        call_entry_point_as(
+            &mut builder,
+            *DEFAULT_PROPOSER_ADDR,
+            "join_challenge",
+            runtime_args! { "challenge_id" => "two-books" },
+        )
+        .expect_success()
+        .commit();
+
+        let leaderboard = |builder: &mut LmdbWasmTestBuilder| -> Vec<(Key, u64)> {
+            query_entry_point(
+                builder,
+                "challenge_leaderboard",
+                runtime_args! { "challenge_id" => "two-books" },
+                RETURNS_LEADERBOARD,
+            )
+        };
+        assert_eq!(leaderboard(&mut builder), vec![(user, 2), (other, 1)]);
+
+        // Revoked completions no longer count towards challenges.
+        for (uri, name) in [
+            ("https://example.com/harrow", "Harrow"),
+            ("https://example.com/gideon", "Gideon"),
+        ] {
+            call_entry_point(
+                &mut builder,
+                "revoke_completion",
+                runtime_args! {
+                    "user" => user,
+                    "media_id" => media_id_hex(4u8, uri, name),
+                    "reason_code" => 1u8,
+                    "cooldown" => 0u64,
+                },
+            )
+            .expect_success()
+            .commit();
+        }
+        assert_eq!(leaderboard(&mut builder), vec![(other, 1)]);
+        let progress: BTreeMap<(String, Key), Vec<String>> =
+            query_contract_value(&builder, "challenge_progress");
+        assert!(progress[&("two-books".to_string(), user)].is_empty());
+    }
+
+    #[test]
+    fn should_keep_user_stats_through_join_and_burn() {
+        let mut builder = install_contract();
+        let (kind, uri, name) = (3u8, "https://example.com/saga", "Saga");
+        let user = Key::Account(*DEFAULT_ACCOUNT_ADDR);
+
+        complete(&mut builder, kind, uri, name);
+
+        call_entry_point(
+            &mut builder,
+            "join_group",
+            runtime_args! { "media_id" => media_id_hex(kind, uri, name) },
+        )
+        .expect_success()
+        .commit();
+
+        call_entry_point(&mut builder, "user_stats", runtime_args! { "user" => user })
+            .expect_success()
+            .commit();
+
+        call_entry_point(
+            &mut builder,
+            "burn",
+            runtime_args! { "token_id" => U256::one() },
+        )
+        .expect_success()
+        .commit();
+
+        call_entry_point(&mut builder, "user_stats", runtime_args! { "user" => user })
+            .expect_success()
+            .commit();
+    }
+
+    #[test]
+    fn should_validate_visibility_and_page_media_completers() {
+        let mut builder = install_contract();
+        let (kind, uri, name) = (1u8, "https://example.com/perfect-blue", "Perfect Blue");
+        let media_id = media_id_hex(kind, uri, name);
+
+        call_entry_point(
+            &mut builder,
+            "set_visibility",
+            runtime_args! { "visibility" => 3u8 },
+        )
+        .expect_failure();
+
+        complete(&mut builder, kind, uri, name);
+
+        call_entry_point(
+            &mut builder,
+            "set_visibility",
+            runtime_args! { "visibility" => 2u8 },
+        )
+        .expect_success()
+        .commit();
+
+        call_entry_point(
+            &mut builder,
+            "media_completers_page",
+            runtime_args! {
+                "media_id" => media_id.clone(),
+                "start" => U256::zero(),
+                "count" => U256::from(10u64),
+            },
+        )
+        .expect_success()
+        .commit();
+        call_entry_point(
+            &mut builder,
+            "media_completer_count",
+            runtime_args! { "media_id" => media_id },
+        )
+        .expect_success()
+        .commit();
+    }
+
+    #[test]
+    fn should_filter_group_follow_and_leaderboard_lists_by_visibility() {
+        let mut builder = install_contract();
+        let owner = Key::Account(*DEFAULT_ACCOUNT_ADDR);
+        let other = Key::Account(*DEFAULT_PROPOSER_ADDR);
+        let (kind, uri, name) = (4u8, "https://example.com/piranesi", "Piranesi");
+        let media_id = media_id_hex(kind, uri, name);
+
+        call_entry_point(
+            &mut builder,
+            "create_challenge",
+            runtime_args! {
+                "challenge_id" => "one-book",
+                "rule" => 2u8,
+                "kind" => kind,
+                "required" => 1u64,
+                "media_ids" => Vec::<String>::new(),
+                "start_time" => 0u64,
+                "end_time" => u64::MAX,
+            },
+        )
+        .expect_success()
+        .commit();
+        complete(&mut builder, kind, uri, name);
+        call_entry_point(
+            &mut builder,
+            "complete_and_register_by_external_id",
+            runtime_args! { "to" => other, "kind" => kind, "uri" => uri, "name" => name },
+        )
+        .expect_success()
+        .commit();
+        for sender in [*DEFAULT_ACCOUNT_ADDR, *DEFAULT_PROPOSER_ADDR] {
+            call_entry_point_as(
+                &mut builder,
+                sender,
+                "join_group",
+                runtime_args! { "media_id" => media_id.clone() },
+            )
+            .expect_success()
+            .commit();
+            call_entry_point_as(
+                &mut builder,
+                sender,
+                "join_challenge",
+                runtime_args! { "challenge_id" => "one-book" },
+            )
+            .expect_success()
+            .commit();
+        }
+        call_entry_point_as(
+            &mut builder,
+            *DEFAULT_PROPOSER_ADDR,
+            "follow",
+            runtime_args! { "user" => owner },
+        )
+        .expect_success()
+        .commit();
+
+        let listed = |builder: &mut LmdbWasmTestBuilder| {
+            let count: U256 = query_entry_point(
+                builder,
+                "group_member_count",
+                runtime_args! { "media_id" => media_id.clone() },
+                RETURNS_U256,
+            );
+            let first: Key = query_entry_point(
+                builder,
+                "group_member_at",
+                runtime_args! { "media_id" => media_id.clone(), "index" => U256::zero() },
+                RETURNS_KEY,
+            );
+            let members: Vec<Key> = query_entry_point(
+                builder,
+                "group_members_page",
+                runtime_args! {
+                    "media_id" => media_id.clone(),
+                    "start" => U256::zero(),
+                    "count" => U256::from(10u64),
+                },
+                RETURNS_KEYS,
+            );
+            let followers: Vec<Key> = query_entry_point(
+                builder,
+                "followers_page",
+                runtime_args! {
+                    "user" => owner,
+                    "start" => U256::zero(),
+                    "count" => U256::from(10u64),
+                },
+                RETURNS_KEYS,
+            );
+            let board: Vec<(Key, u64)> = query_entry_point(
+                builder,
+                "challenge_leaderboard",
+                runtime_args! { "challenge_id" => "one-book" },
+                RETURNS_LEADERBOARD,
+            );
+            let board: Vec<Key> = board.into_iter().map(|(user, _)| user).collect();
+            (count, first, members, followers, board)
+        };
+
+        let set_visibility = |builder: &mut LmdbWasmTestBuilder, visibility: u8| {
+            call_entry_point_as(
+                builder,
+                *DEFAULT_PROPOSER_ADDR,
+                "set_visibility",
+                runtime_args! { "visibility" => visibility },
+            )
+            .expect_success()
+            .commit();
+        };
+
+        set_visibility(&mut builder, 2);
+        let (count, first, members, followers, board) = listed(&mut builder);
+        assert_eq!(count, U256::from(2u64));
+        assert_eq!(first, owner);
+        assert_eq!(members, vec![owner]);
+        assert!(followers.is_empty());
+        assert_eq!(board, vec![owner]);
+
+        set_visibility(&mut builder, 1);
+        let (count, _first, members, followers, board) = listed(&mut builder);
+        assert_eq!(count, U256::from(2u64));
+        assert_eq!(members.len(), 2);
+        assert_eq!(followers, vec![other]);
+        assert_eq!(board.len(), 2);
+        assert!(board.contains(&other));
+    }
+
+    #[test]
+    fn should_reveal_private_completion_for_group_access() {
+        let mut builder = install_contract();
+        let user = Key::Account(*DEFAULT_ACCOUNT_ADDR);
+        let media_id = media_id_hex(4u8, "https://example.com/giovanni", "Giovanni's Room");
+        let salt = "3f9c1a7e";
+        let commitment = commitment_hex(user, &media_id, salt);
+
+        call_entry_point(
+            &mut builder,
+            "complete_private",
+            runtime_args! { "commitment" => "not-a-commitment" },
+        )
+        .expect_failure();
+        call_entry_point(
+            &mut builder,
+            "complete_private",
+            runtime_args! { "commitment" => commitment.clone() },
+        )
+        .expect_success()
+        .commit();
+        call_entry_point(
+            &mut builder,
+            "complete_private",
+            runtime_args! { "commitment" => commitment },
+        )
+        .expect_failure();
+
+        call_entry_point(
+            &mut builder,
+            "join_group",
+            runtime_args! { "media_id" => media_id.clone() },
+        )
+        .expect_failure();
+
+        call_entry_point(
+            &mut builder,
+            "prove_completion",
+            runtime_args! {
+                "user" => user,
+                "media_id" => media_id.clone(),
+                "salt" => "wrong-salt",
+            },
+        )
+        .expect_failure();
+        call_entry_point(
+            &mut builder,
+            "prove_completion",
+            runtime_args! {
+                "user" => user,
+                "media_id" => media_id.clone(),
+                "salt" => salt,
+            },
+        )
+        .expect_success()
+        .commit();
+
+        call_entry_point(
+            &mut builder,
+            "join_group",
+            runtime_args! { "media_id" => media_id },
+        )
+        .expect_success()
+        .commit();
+    }
+
+    #[test]
+    fn should_limit_and_revoke_private_completions() {
+        let mut builder = install_contract();
+        let user = Key::Account(*DEFAULT_ACCOUNT_ADDR);
+        let media_id = media_id_hex(4u8, "https://example.com/giovanni", "Giovanni's Room");
+        let other_media_id = media_id_hex(4u8, "https://example.com/orlando", "Orlando");
+        let salt = "3f9c1a7e";
+
+        call_entry_point(
+            &mut builder,
+            "set_mint_limits",
+            runtime_args! {
+                "window" => 86_400_000u64,
+                "per_user_limit" => 0u64,
+                "per_minter_limit" => 1u64,
+            },
+        )
+        .expect_success()
+        .commit();
+        call_entry_point(
+            &mut builder,
+            "complete_private",
+            runtime_args! { "commitment" => commitment_hex(user, &media_id, salt) },
+        )
+        .expect_success()
+        .commit();
+        call_entry_point(
+            &mut builder,
+            "complete_private",
+            runtime_args! { "commitment" => commitment_hex(user, &other_media_id, salt) },
+        )
+        .expect_failure();
+
+        let prove_args = || {
+            runtime_args! {
+                "user" => user,
+                "media_id" => media_id.clone(),
+                "salt" => salt,
+            }
+        };
+        call_entry_point(&mut builder, "prove_completion", prove_args())
+            .expect_success()
+            .commit();
+        let revealed: BTreeMap<(Key, String), u64> =
+            query_contract_value(&builder, "revealed_completions");
+        assert!(revealed.contains_key(&(user, media_id.clone())));
+        call_entry_point(
+            &mut builder,
+            "join_group",
+            runtime_args! { "media_id" => media_id.clone() },
+        )
+        .expect_success()
+        .commit();
+
+        call_entry_point(
+            &mut builder,
+            "revoke_completion",
+            runtime_args! {
+                "user" => user,
+                "media_id" => media_id.clone(),
+                "reason_code" => 1u8,
+                "cooldown" => 86_400_000u64,
+            },
+        )
+        .expect_success()
+        .commit();
+        let revealed: BTreeMap<(Key, String), u64> =
+            query_contract_value(&builder, "revealed_completions");
+        assert!(revealed.is_empty());
+
+        let is_member: bool = query_entry_point(
+            &mut builder,
+            "is_group_member",
+            runtime_args! { "media_id" => media_id.clone(), "user" => user },
+            RETURNS_BOOL,
+        );
+        assert!(!is_member);
+
+        call_entry_point(&mut builder, "prove_completion", prove_args()).expect_failure();
+        let error = builder.get_error().expect("should have error");
+        assert!(format!("{:?}", error).contains("ApiError::User(36)"));
+
+        // The revoked commitment stays dead after the cooldown; only a new one counts.
+        let after_cooldown = 2 * 86_400_000u64;
+        call_entry_point_at(
+            &mut builder,
+            after_cooldown,
+            "prove_completion",
+            prove_args(),
+        )
+        .expect_failure();
+        let error = builder.get_error().expect("should have error");
+        assert!(format!("{:?}", error).contains("ApiError::User(65)"));
+
+        let new_salt = "c04d2b19";
+        call_entry_point_at(
+            &mut builder,
+            after_cooldown,
+            "complete_private",
+            runtime_args! { "commitment" => commitment_hex(user, &media_id, new_salt) },
+        )
+        .expect_success()
+        .commit();
+        call_entry_point_at(
+            &mut builder,
+            after_cooldown,
+            "prove_completion",
+            runtime_args! {
+                "user" => user,
+                "media_id" => media_id.clone(),
+                "salt" => new_salt,
+            },
+        )
+        .expect_success()
+        .commit();
+    }
+
+    #[test]
+    fn should_join_group_and_burn_through_contract_package_holder() {
+        let mut builder = install_contract();
+        let wallet_hash = install_wallet(&mut builder);
+        let wallet = Key::Hash(get_named_contract_hash(&builder, WALLET_PACKAGE_NAME).value());
+        assert_wallet_can_join_group_and_burn(&mut builder, wallet_hash, wallet);
+    }
+
+    #[test]
+    fn should_join_group_and_burn_through_addressable_entity_holder() {
+        let chainspec = ChainspecConfig::from_chainspec_path(&*CHAINSPEC_SYMLINK)
+            .expect("should load chainspec")
+            .with_enable_addressable_entity(true);
+        let mut builder = LmdbWasmTestBuilder::new_temporary_with_config(chainspec);
+        let mut genesis_request = LOCAL_GENESIS_REQUEST.clone();
+        genesis_request.set_enable_entity(true);
+        builder.run_genesis(genesis_request);
+        let install_request =
+            ExecuteRequestBuilder::standard(*DEFAULT_ACCOUNT_ADDR, WASM_FILE, runtime_args! {})
+                .build();
+        builder.exec(install_request).expect_success().commit();
+
+        let wallet_hash = install_wallet(&mut builder);
+        let wallet = Key::AddressableEntity(EntityAddr::SmartContract(wallet_hash.value()));
+        assert_wallet_can_join_group_and_burn(&mut builder, wallet_hash, wallet);
+    }
+
+    fn install_contract() -> LmdbWasmTestBuilder {
+        std::fs::create_dir_all("wasm").unwrap();
+        let mut builder = LmdbWasmTestBuilder::default();
+        builder.run_genesis(LOCAL_GENESIS_REQUEST.clone());
+
+        let install_request =
+            ExecuteRequestBuilder::standard(*DEFAULT_ACCOUNT_ADDR, WASM_FILE, runtime_args! {})
+                .build();
+
+        builder.exec(install_request).expect_success().commit();
+        builder
+    }
+
+    fn call_entry_point<'a>(
+        builder: &'a mut LmdbWasmTestBuilder,
+        entry_point: &str,
+        args: RuntimeArgs,
+    ) -> &'a mut LmdbWasmTestBuilder {
+        call_entry_point_as(builder, *DEFAULT_ACCOUNT_ADDR, entry_point, args)
+    }
+
+    fn call_entry_point_at<'a>(
+        builder: &'a mut LmdbWasmTestBuilder,
+        block_time: u64,
+        entry_point: &str,
+        args: RuntimeArgs,
+    ) -> &'a mut LmdbWasmTestBuilder {
+        let request = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            get_contract_hash(builder).into(),
+            entry_point,
+            args,
+        )
+        .with_block_time(block_time)
+        .build();
+
+        builder.exec(request)
+    }
+
+    fn call_entry_point_as<'a>(
+        builder: &'a mut LmdbWasmTestBuilder,
+        sender: AccountHash,
+        entry_point: &str,
+        args: RuntimeArgs,
+    ) -> &'a mut LmdbWasmTestBuilder {
+        let request = ExecuteRequestBuilder::contract_call_by_hash(
+            sender,
+            get_contract_hash(builder).into(),
+            entry_point,
+            args,
+        )
+        .build();
+
+        builder.exec(request)
+    }
+
+    fn query_entry_point<T: CLTyped + FromBytes>(
+        builder: &mut LmdbWasmTestBuilder,
+        entry_point: &str,
+        args: RuntimeArgs,
+        returns: u8,
+    ) -> T {
+        let request = ExecuteRequestBuilder::standard(
+            *DEFAULT_ACCOUNT_ADDR,
+            QUERY_WASM_FILE,
+            runtime_args! {
+                "media_nft" => Key::from(get_contract_hash(builder)),
+                "entry_point" => entry_point,
+                "args" => Bytes::from(args.to_bytes().unwrap()),
+                "returns" => returns,
+            },
+        )
+        .build();
+        builder.exec(request).expect_success().commit();
+        builder
+            .query(
+                None,
+                Key::Account(*DEFAULT_ACCOUNT_ADDR),
+                &["query_result".to_string()],
+            )
+            .expect("should have query result")
+            .as_cl_value()
+            .cloned()
+            .expect("should be a CLValue")
+            .into_t()
+            .expect("should have the expected type")
+    }
+
+    fn install_wallet(builder: &mut LmdbWasmTestBuilder) -> ContractHash {
+        let install_wallet = ExecuteRequestBuilder::standard(
+            *DEFAULT_ACCOUNT_ADDR,
+            WALLET_WASM_FILE,
+            runtime_args! {},
+        )
+        .build();
+        builder.exec(install_wallet).expect_success().commit();
+        get_named_contract_hash(builder, WALLET_CONTRACT_NAME)
+    }
+
+    fn assert_wallet_can_join_group_and_burn(
+        builder: &mut LmdbWasmTestBuilder,
+        wallet_hash: ContractHash,
+        wallet: Key,
+    ) {
+        let media_nft = Key::from(get_contract_hash(builder));
+        let (kind, uri, name) = (1u8, "https://example.com/matrix", "The Matrix");
+        let media_id = media_id_hex(kind, uri, name);
+        call_entry_point(
+            builder,
+            "complete_and_register_by_external_id",
+            runtime_args! {
+                "to" => wallet,
+                "kind" => kind,
+                "uri" => uri,
+                "name" => name,
+            },
+        )
+        .expect_success()
+        .commit();
+
+        let token_ids: BTreeMap<Key, Vec<U256>> = query_contract_value(builder, "user_token_ids");
+        let token_id = token_ids[&wallet][0];
+
+        call_entry_point(builder, "burn", runtime_args! { "token_id" => token_id })
+            .expect_failure();
+
+        let wallet_call = |entry_point: &str, args: RuntimeArgs| {
+            ExecuteRequestBuilder::contract_call_by_hash(
+                *DEFAULT_ACCOUNT_ADDR,
+                wallet_hash.into(),
+                entry_point,
+                args,
+            )
+            .build()
+        };
+
+        builder
+            .exec(wallet_call(
+                "join_group",
+                runtime_args! { "media_nft" => media_nft, "media_id" => media_id.clone() },
+            ))
+            .expect_success()
+            .commit();
+        let members: BTreeMap<String, Vec<Key>> = query_contract_value(builder, "group_members");
+        assert_eq!(members[&media_id], vec![wallet]);
+
+        builder
+            .exec(wallet_call(
+                "burn",
+                runtime_args! { "media_nft" => media_nft, "token_id" => token_id },
+            ))
+            .expect_success()
+            .commit();
+        let token_ids: BTreeMap<Key, Vec<U256>> = query_contract_value(builder, "user_token_ids");
+        assert!(token_ids.get(&wallet).is_none_or(Vec::is_empty));
+    }
+
+    fn complete(builder: &mut LmdbWasmTestBuilder, kind: u8, uri: &str, name: &str) {
+        call_entry_point(
+            builder,
+            "complete_and_register_by_external_id",
+            runtime_args! {
+                "to" => Key::Account(*DEFAULT_ACCOUNT_ADDR),
+                "kind" => kind,
+                "uri" => uri,
+                "name" => name,
+            },
+        )
+        .expect_success()
+        .commit();
+    }
+
+    fn media_id_hex(kind: u8, uri: &str, name: &str) -> String {
+        let mut bytes = Vec::new();
+        bytes.push(kind);
+        bytes.extend_from_slice(uri.as_bytes());
+        bytes.push(0);
+        bytes.extend_from_slice(name.as_bytes());
+        blake2b_hex(&bytes)
+    }
+
+    fn room_id_hex(media_id: &str, name: &str) -> String {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(media_id.as_bytes());
+        bytes.push(0);
+        bytes.extend_from_slice(name.as_bytes());
+        blake2b_hex(&bytes)
+    }
+
+    fn group_id_hex(name: &str) -> String {
+        let mut bytes = Key::Account(*DEFAULT_ACCOUNT_ADDR).to_bytes().unwrap();
+        bytes.push(0);
+        bytes.extend_from_slice(name.as_bytes());
+        blake2b_hex(&bytes)
+    }
+
+    fn commitment_hex(user: Key, media_id: &str, salt: &str) -> String {
+        let mut bytes = user.to_bytes().unwrap();
+        bytes.push(0);
+        bytes.extend_from_slice(media_id.as_bytes());
+        bytes.push(0);
+        bytes.extend_from_slice(salt.as_bytes());
+        blake2b_hex(&bytes)
+    }
+
+    fn blake2b_hex(bytes: &[u8]) -> String {
+        let mut digest = vec![0u8; 32];
+        let mut hasher = blake2::Blake2bVar::new(32).unwrap();
+        hasher.update(bytes);
+        hasher.finalize_variable(&mut digest).unwrap();
+        let mut out = String::with_capacity(64);
+        for b in digest.iter() {
+            out.push_str(&format!("{:02x}", b));
+        }
+        out
+    }
+
+    fn get_named_contract_hash(builder: &LmdbWasmTestBuilder, name: &str) -> ContractHash {
+        builder
+            .get_entity_with_named_keys_by_account_hash(*DEFAULT_ACCOUNT_ADDR)
+            .expect("should have account")
+            .named_keys()
+            .get(name)
+            .and_then(|key| key.into_hash_addr())
+            .map(ContractHash::new)
+            .expect("should have contract hash")
+    }
+
+    fn query_contract_value<T: CLTyped + FromBytes>(
+        builder: &LmdbWasmTestBuilder,
+        name: &str,
+    ) -> T {
+        query_named_value(builder, get_contract_hash(builder), name)
+    }
+
+    fn query_named_value<T: CLTyped + FromBytes>(
+        builder: &LmdbWasmTestBuilder,
+        contract_hash: ContractHash,
+        name: &str,
+    ) -> T {
+        let contract = if builder.chainspec().core_config.enable_addressable_entity {
+            Key::AddressableEntity(EntityAddr::SmartContract(contract_hash.value()))
+        } else {
+            Key::from(contract_hash)
+        };
+        builder
+            .query(None, contract, &[name.to_string()])
+            .expect("should have named key")
+            .as_cl_value()
+            .cloned()
+            .expect("should be a CLValue")
+            .into_t()
+            .expect("should have the expected type")
+    }
+
+    fn get_contract_hash(builder: &LmdbWasmTestBuilder) -> ContractHash {
+        builder
+            .get_entity_with_named_keys_by_account_hash(*DEFAULT_ACCOUNT_ADDR)
             .expect("should have account")
             .named_keys()
             .get(CONTRACT_NAME)